@@ -1,5 +1,6 @@
-use std::{path::PathBuf, process::Command, sync::LazyLock};
+use std::{fmt, path::PathBuf, process::Command, sync::LazyLock};
 
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use tokio::fs;
 
@@ -20,14 +21,55 @@ struct Player {
     args: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, Default)]
 struct Bilibili {
     cookie: String,
+    /// 扫码登录时获得，用于刷新 cookie
+    #[serde(default)]
+    refresh_token: String,
+}
+
+// 配置会被写入日志，不能输出 cookie 和 refresh_token 的内容
+impl fmt::Debug for Bilibili {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bilibili")
+            .field("cookie", &format!("<{} bytes>", self.cookie.len()))
+            .field(
+                "refresh_token",
+                &format!("<{} bytes>", self.refresh_token.len()),
+            )
+            .finish()
+    }
+}
+
+/// YY 客户端身份，首次解析时生成，每个安装保持不变
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct Yy {
+    uid: u64,
+}
+
+/// CHZZK 使用 Naver 账号的 cookie，观看年龄限制的直播需要
+#[derive(Serialize, Deserialize, Default)]
+struct Chzzk {
+    /// `NID_AUT` 和 `NID_SES`
+    cookie: String,
+}
+
+impl fmt::Debug for Chzzk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chzzk")
+            .field("cookie", &format!("<{} bytes>", self.cookie.len()))
+            .finish()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 struct Platform {
     bilibili: Bilibili,
+    #[serde(default)]
+    yy: Yy,
+    #[serde(default)]
+    chzzk: Chzzk,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -93,8 +135,59 @@ impl Config {
 
         Ok(config)
     }
+
+    async fn write_to_file(&self) -> LsarResult<()> {
+        let data = toml::to_string(self).map_err(|e| {
+            error!("Failed to serialize config: {:?}", e);
+            e
+        })?;
+
+        fs::write(&*CONFIG_FILE_PATH, data).await.map_err(|e| {
+            error!("Failed to write config file: {:?}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    pub fn bilibili_refresh_token(&self) -> &str {
+        &self.platform.bilibili.refresh_token
+    }
+
+    pub fn chzzk_cookie(&self) -> &str {
+        &self.platform.chzzk.cookie
+    }
+
+    /// 获取 YY 的匿名 uid，不存在时生成并写入配置文件
+    pub async fn yy_uid() -> LsarResult<u64> {
+        let mut config = Self::read_from_file()?;
+        if config.platform.yy.uid != 0 {
+            return Ok(config.platform.yy.uid);
+        }
+
+        let uid = rand::rng().random_range(YY_UID_RANGE);
+        config.platform.yy.uid = uid;
+        config.write_to_file().await?;
+
+        info!("Generated YY uid: {}", uid);
+        Ok(uid)
+    }
+
+    /// 保存扫码登录得到的 cookie 和 refresh_token
+    pub async fn save_bilibili_credential(cookie: String, refresh_token: String) -> LsarResult<()> {
+        let mut config = Self::read_from_file()?;
+        config.platform.bilibili.cookie = cookie;
+        config.platform.bilibili.refresh_token = refresh_token;
+        config.write_to_file().await?;
+
+        info!("Saved Bilibili credential to config file");
+        Ok(())
+    }
 }
 
+/// 与网页端匿名用户的 uid 位数一致
+const YY_UID_RANGE: std::ops::RangeInclusive<u64> = 1_000_000_000..=4_294_967_295;
+
 static CONFIG_FILE_PATH: LazyLock<PathBuf> = LazyLock::new(|| APP_CONFIG_DIR.join("lsar.toml"));
 
 #[tauri::command]
@@ -103,17 +196,26 @@ pub async fn read_config_file() -> LsarResult<Config> {
 }
 
 #[tauri::command]
-pub async fn write_config_file(config: Config) -> LsarResult<()> {
+pub async fn write_config_file(mut config: Config) -> LsarResult<()> {
     debug!("Writing new config: {:?}", config);
-    let data = toml::to_string(&config).map_err(|e| {
-        error!("Failed to serialize config: {:?}", e);
-        e
-    })?;
-
-    fs::write(&*CONFIG_FILE_PATH, data).await.map_err(|e| {
-        error!("Failed to write config file: {:?}", e);
-        e
-    })?;
+
+    // 前端的配置可能是生成 YY uid 之前读取的，不能覆盖已生成的 uid
+    if config.platform.yy.uid == 0 {
+        if let Ok(current) = Config::read_from_file() {
+            config.platform.yy.uid = current.platform.yy.uid;
+        }
+    }
+
+    // 同样，前端的配置可能是扫码登录之前读取的，不能用空的 refresh_token 覆盖已保存的，
+    // 只有清空 cookie（退出登录）时才一并清空
+    let bilibili = &mut config.platform.bilibili;
+    if bilibili.refresh_token.is_empty() && !bilibili.cookie.is_empty() {
+        if let Ok(current) = Config::read_from_file() {
+            bilibili.refresh_token = current.platform.bilibili.refresh_token;
+        }
+    }
+
+    config.write_to_file().await?;
 
     info!("Successfully wrote new config to file");
 
@@ -34,26 +34,29 @@ async fn get_global_pool() -> &'static SqlitePool {
         .await
 }
 
+/// 房间号为文本，部分平台（如 Twitch）的频道名不是数字
+const CREATE_HISTORY_TABLE: &str = "CREATE TABLE IF NOT EXISTS history (
+    id              INTEGER PRIMARY KEY,
+    platform        INTEGER NOT NULL,
+    room_id         TEXT NOT NULL,
+    anchor          TEXT NOT NULL,
+    category        TEXT NOT NULL,
+    last_title      TEXT NOT NULL,
+    last_play_time  DATETIME NOT NULL
+)";
+
 async fn create_history_table(pool: &SqlitePool) -> LsarResult<()> {
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS history (
-            id              INTEGER PRIMARY KEY,
-            platform        INTEGER NOT NULL,
-            room_id         INTEGER NOT NULL,
-            anchor          TEXT NOT NULL,
-            category        TEXT NOT NULL,
-            last_title      TEXT NOT NULL,
-            last_play_time  DATETIME NOT NULL
-        )",
-    )
-    .execute(pool)
-    .await
-    .map_err(|e| {
-        error!("Failed to create history table: {:?}", e);
-        e
-    })?;
+    sqlx::query(CREATE_HISTORY_TABLE)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to create history table: {:?}", e);
+            e
+        })?;
     info!("History table created or already exists");
 
+    migrate_room_id_to_text(pool).await?;
+
     sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_unique_platform_room_id ON history (platform, room_id);")
         .execute(pool)
         .await
@@ -66,13 +69,59 @@ async fn create_history_table(pool: &SqlitePool) -> LsarResult<()> {
     Ok(())
 }
 
+/// 旧版本的 `room_id` 为 INTEGER，SQLite 会把写入的数字字符串转换回整数，需要重建表
+async fn migrate_room_id_to_text(pool: &SqlitePool) -> LsarResult<()> {
+    let (column_type,): (String,) =
+        sqlx::query_as("SELECT type FROM pragma_table_info('history') WHERE name = 'room_id'")
+            .fetch_one(pool)
+            .await?;
+    if !column_type.eq_ignore_ascii_case("INTEGER") {
+        return Ok(());
+    }
+
+    info!("Migrating history.room_id from INTEGER to TEXT");
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("ALTER TABLE history RENAME TO history_old")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(CREATE_HISTORY_TABLE).execute(&mut *tx).await?;
+    sqlx::query(
+        "INSERT INTO history (id, platform, room_id, anchor, category, last_title, last_play_time)
+         SELECT id, platform, CAST(room_id AS TEXT), anchor, category, last_title, last_play_time
+         FROM history_old",
+    )
+    .execute(&mut *tx)
+    .await?;
+    // 旧表上的唯一索引随旧表一起删除，之后在新表上重新创建
+    sqlx::query("DROP TABLE history_old")
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await.map_err(|e| {
+        error!("Failed to migrate history table: {:?}", e);
+        e
+    })?;
+    info!("Migrated history.room_id to TEXT");
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_all_history() -> LsarResult<Vec<HistoryItem>> {
     debug!("Fetching all history records");
 
     let pool = get_global_pool().await;
 
-    let rows: Vec<(i64, i64, i64, String, String, String, time::OffsetDateTime)> = sqlx::query_as(
+    let rows: Vec<(
+        i64,
+        i64,
+        String,
+        String,
+        String,
+        String,
+        time::OffsetDateTime,
+    )> = sqlx::query_as(
         "SELECT id, platform, room_id, anchor, category, last_title, last_play_time
          FROM history
          ORDER BY last_play_time DESC;",
@@ -168,3 +217,104 @@ pub async fn insert_a_history(history: HistoryItem) -> LsarResult<()> {
 
     Ok(())
 }
+
+/// 批量导入历史记录，已存在的记录只更新分类和标题，保留原来的播放时间
+pub async fn import_histories(items: &[HistoryItem]) -> LsarResult<u64> {
+    debug!("Importing {} history records", items.len());
+
+    let pool = get_global_pool().await;
+    let mut tx = pool.begin().await?;
+    let mut affected = 0;
+
+    for history in items {
+        let result = sqlx::query(
+            r#"
+    INSERT INTO history (platform, room_id, anchor, category, last_title, last_play_time)
+    VALUES (?, ?, ?, ?, ?, ?)
+    ON CONFLICT(platform, room_id) DO UPDATE SET
+    category = excluded.category,
+    last_title = excluded.last_title
+    "#,
+        )
+        .bind(history.platform().as_i64())
+        .bind(history.room_id())
+        .bind(history.anchor())
+        .bind(history.category())
+        .bind(history.last_title())
+        .bind(history.last_play_time())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to import history record: {:?}", e);
+            e
+        })?;
+
+        affected += result.rows_affected();
+    }
+
+    tx.commit().await?;
+    info!("Imported {} history records", affected);
+
+    Ok(affected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_migrate_room_id_to_text() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::query(
+            "CREATE TABLE history (
+                id              INTEGER PRIMARY KEY,
+                platform        INTEGER NOT NULL,
+                room_id         INTEGER NOT NULL,
+                anchor          TEXT NOT NULL,
+                category        TEXT NOT NULL,
+                last_title      TEXT NOT NULL,
+                last_play_time  DATETIME NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO history (platform, room_id, anchor, category, last_title, last_play_time)
+             VALUES (0, 9999, 'anchor', 'category', 'title', '2024-01-01T00:00:00Z')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        create_history_table(&pool).await.unwrap();
+        // 再次执行时不会重复迁移
+        create_history_table(&pool).await.unwrap();
+
+        let (room_id,): (String,) = sqlx::query_as("SELECT room_id FROM history")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(room_id, "9999");
+
+        sqlx::query(
+            "INSERT INTO history (platform, room_id, anchor, category, last_title, last_play_time)
+             VALUES (7, 'shroud', 'shroud', 'VALORANT', 'title', '2024-01-01T00:00:00Z')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let duplicated = sqlx::query(
+            "INSERT INTO history (platform, room_id, anchor, category, last_title, last_play_time)
+             VALUES (0, '9999', 'anchor', 'category', 'title', '2024-01-01T00:00:00Z')",
+        )
+        .execute(&pool)
+        .await;
+        assert!(duplicated.is_err(), "unique index should be recreated");
+    }
+}
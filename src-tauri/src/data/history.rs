@@ -7,7 +7,7 @@ use crate::platform::Platform;
 pub struct HistoryItem {
     id: i64,
     platform: Platform,
-    room_id: i64,
+    room_id: String,
     anchor: String,
     category: String,
     last_title: String,
@@ -19,7 +19,7 @@ impl HistoryItem {
     pub fn new(
         id: i64,
         platform: Platform,
-        room_id: i64,
+        room_id: String,
         anchor: String,
         category: String,
         last_title: String,
@@ -44,8 +44,8 @@ impl HistoryItem {
         &self.platform
     }
 
-    pub fn room_id(&self) -> i64 {
-        self.room_id
+    pub fn room_id(&self) -> &str {
+        &self.room_id
     }
 
     pub fn anchor(&self) -> &str {
@@ -65,14 +65,14 @@ impl HistoryItem {
     }
 }
 
-impl TryFrom<(i64, i64, i64, String, String, String, OffsetDateTime)> for HistoryItem {
+impl TryFrom<(i64, i64, String, String, String, String, OffsetDateTime)> for HistoryItem {
     type Error = &'static str;
 
     fn try_from(
         (id, platform, room_id, anchor, category, last_title, last_play_time): (
             i64,
             i64,
-            i64,
+            String,
             String,
             String,
             String,
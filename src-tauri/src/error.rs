@@ -98,6 +98,17 @@ pub(super) enum RoomStateError {
     NotExists,
     // IsClosed, // 抖音直播间状态，暂时未使用
     IsReplay,
+    /// 房间被锁定
+    Locked,
+    /// 加密房间，需要密码
+    PasswordRequired,
+    WrongPassword,
+    /// 付费直播，当前账号没有观看权限
+    Paid,
+    /// 平台限制了当前地区或账号观看
+    Restricted,
+    /// 仅限会员观看的直播
+    MembersOnly,
 }
 
 impl fmt::Display for RoomStateError {
@@ -107,6 +118,12 @@ impl fmt::Display for RoomStateError {
             RoomStateError::NotExists => "房间号不存在",
             // RoomStateError::IsClosed => "该房间已被关闭",
             RoomStateError::IsReplay => "该房间正在重播",
+            RoomStateError::Locked => "该房间已被锁定",
+            RoomStateError::PasswordRequired => "该房间已加密，需要输入房间密码",
+            RoomStateError::WrongPassword => "房间密码错误",
+            RoomStateError::Paid => "该房间为付费直播，当前账号没有观看权限",
+            RoomStateError::Restricted => "该直播间在当前地区或当前账号下不可观看",
+            RoomStateError::MembersOnly => "该直播仅限会员观看",
         };
         write!(f, "{}", string)
     }
@@ -115,12 +132,18 @@ impl fmt::Display for RoomStateError {
 #[derive(Debug, Serialize, thiserror::Error)]
 pub(super) enum RequestError {
     BadRequest,
+    /// 触发平台风控，需要在浏览器中完成验证
+    Captcha,
+    /// 平台要求登录后才能观看
+    LoginRequired,
 }
 
 impl fmt::Display for RequestError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             RequestError::BadRequest => "非法请求",
+            RequestError::Captcha => "触发了平台的安全验证，请在浏览器中打开直播间完成验证后重试",
+            RequestError::LoginRequired => "该直播间需要登录后才能观看",
         };
         write!(f, "{}", s)
     }
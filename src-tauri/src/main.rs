@@ -31,7 +31,13 @@ use crate::data::db::{delete_a_history_by_id, get_all_history, insert_a_history}
 use crate::error::LsarResult;
 use crate::http::{get, post};
 use crate::log::{debug, error, info, trace, warn};
-use crate::parsers::{parse_bigo, parse_bilibili, parse_douyin, parse_douyu, parse_huya, parse_yy};
+use crate::parsers::{
+    generate_bilibili_qrcode, get_bilibili_followed_live_rooms, get_huya_link_lifetime,
+    parse_acfun, parse_bigo, parse_bilibili, parse_cc, parse_chzzk, parse_douyin, parse_douyu,
+    parse_huya, parse_kuaishou, parse_niconico, parse_soop, parse_tiktok, parse_twitcasting,
+    parse_twitch, parse_youtube, parse_yy, poll_bilibili_qrcode, refresh_huya_link,
+    stop_niconico_keep_alive,
+};
 use crate::path::get_player_paths;
 use crate::setup::{setup_app, setup_logging};
 use crate::utils::md5;
@@ -114,9 +120,25 @@ fn main() {
             parse_bigo,
             parse_douyu,
             parse_huya,
+            refresh_huya_link,
+            get_huya_link_lifetime,
             parse_douyin,
             parse_bilibili,
+            generate_bilibili_qrcode,
+            poll_bilibili_qrcode,
+            get_bilibili_followed_live_rooms,
             parse_yy,
+            parse_kuaishou,
+            parse_twitch,
+            parse_youtube,
+            parse_cc,
+            parse_acfun,
+            parse_soop,
+            parse_chzzk,
+            parse_tiktok,
+            parse_niconico,
+            stop_niconico_keep_alive,
+            parse_twitcasting,
             get_player_paths,
             #[cfg(windows)]
             set_titlebar_color_mode
@@ -0,0 +1,212 @@
+mod visitor;
+
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, REFERER, SET_COOKIE};
+use serde_json::Value;
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, RoomStateError};
+use crate::network::http::Client;
+use crate::parsers::{Link, ParsedResult, Parser, Quality};
+use crate::platform::Platform;
+
+use self::visitor::{generate_did, Visitor, VisitorLogin};
+
+const LIVE_BASE_URL: &str = "https://live.acfun.cn/live/";
+const START_PLAY_URL: &str = "https://api.kuaishouzt.com/rest/zt/live/web/startPlay";
+const USER_INFO_URL: &str = "https://live.acfun.cn/rest/pc-direct/user/userInfo";
+
+/// `startPlay` 成功时的 `result`
+const RESULT_OK: i64 = 1;
+/// 主播未开播
+const RESULT_OFFLINE: i64 = 380023;
+
+pub struct AcfunParser {
+    /// 主播 uid，即直播间链接中的数字
+    room_id: u64,
+    http_client: Client,
+}
+
+impl AcfunParser {
+    fn new(room_id: u64) -> Self {
+        Self {
+            room_id,
+            http_client: Client::new(),
+        }
+    }
+
+    /// 访问直播间页面获取 `_did`，没有时自行生成
+    async fn get_did(&self) -> LsarResult<String> {
+        let response = self
+            .http_client
+            .get(&format!("{}{}", LIVE_BASE_URL, self.room_id))
+            .await?;
+
+        let did = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok()?.strip_prefix("_did="))
+            .filter_map(|v| v.split(';').next())
+            .map(str::to_string)
+            .next()
+            .unwrap_or_else(generate_did);
+        debug!("AcFun did: {}", did);
+
+        Ok(did)
+    }
+
+    async fn start_play(&self, visitor: &Visitor) -> LsarResult<Value> {
+        let mut url = Url::parse(START_PLAY_URL)?;
+        url.query_pairs_mut()
+            .append_pair("subBiz", "mainApp")
+            .append_pair("kpn", "ACFUN_APP")
+            .append_pair("kpf", "PC_WEB")
+            .append_pair("userId", &visitor.user_id.to_string())
+            .append_pair("did", &visitor.did)
+            .append_pair("acfun.api.visitor_st", &visitor.token);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(REFERER, HeaderValue::from_static("https://live.acfun.cn/"));
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&format!("_did={}", visitor.did)).map_err(|e| e.to_string())?,
+        );
+
+        let author_id = self.room_id.to_string();
+        let body = [("authorId", author_id.as_str()), ("pullStreamType", "FLV")];
+        let resp: Value = self
+            .http_client
+            .post_form(url.as_str(), &body, headers)
+            .await?;
+        trace!("AcFun startPlay response: {}", resp);
+
+        Ok(resp)
+    }
+
+    async fn get_anchor_name(&self) -> LsarResult<String> {
+        let url = format!("{}?userId={}", USER_INFO_URL, self.room_id);
+        let resp: Value = self.http_client.get_json(&url, None).await?;
+
+        Ok(resp["profile"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+}
+
+/// `videoPlayRes` 是 JSON 字符串，其中的 `representation` 即各个画质
+fn parse_play_res(resp: &Value) -> LsarResult<(Vec<Link>, Vec<Quality>)> {
+    match resp["result"].as_i64() {
+        Some(RESULT_OK) => {}
+        Some(RESULT_OFFLINE) => {
+            info!("AcFun anchor is offline");
+            return Err(RoomStateError::Offline.into());
+        }
+        _ => {
+            error!("AcFun startPlay failed: {}", resp);
+            return Err(LsarError::Other(
+                resp["error_msg"]
+                    .as_str()
+                    .unwrap_or("AcFun 获取直播流失败")
+                    .to_string(),
+            ));
+        }
+    }
+
+    let play_res: Value = serde_json::from_str(
+        resp["data"]["videoPlayRes"]
+            .as_str()
+            .ok_or_else(|| LsarError::from("Failed to find videoPlayRes"))?,
+    )?;
+
+    let representations = play_res["liveAdaptiveManifest"][0]["adaptationSet"]["representation"]
+        .as_array()
+        .ok_or(RoomStateError::Offline)?;
+
+    let mut links = Vec::new();
+    let mut qualities = Vec::new();
+    for representation in representations.iter().rev() {
+        let (Some(url), Some(name)) = (
+            representation["url"].as_str(),
+            representation["name"].as_str(),
+        ) else {
+            continue;
+        };
+
+        links.push(Link::new(url).quality(name).format("FLV"));
+        qualities.push(Quality::new(
+            representation["bitrate"].as_i64().unwrap_or_default(),
+            name,
+        ));
+    }
+
+    Ok((links, qualities))
+}
+
+impl Parser for AcfunParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let did = self.get_did().await?;
+        let visitor = VisitorLogin::new(&self.http_client).login(did).await?;
+        let resp = self.start_play(&visitor).await?;
+        let (links, qualities) = parse_play_res(&resp)?;
+
+        Ok(ParsedResult {
+            platform: Platform::Acfun,
+            title: resp["data"]["caption"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            anchor: self.get_anchor_name().await?,
+            room_id: self.room_id.to_string(),
+            category: String::new(),
+            links,
+            qualities,
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+}
+
+#[tauri::command]
+pub async fn parse_acfun(room_id: u64) -> LsarResult<ParsedResult> {
+    info!("Parsing AcFun live: {}", room_id);
+    let mut acfun = AcfunParser::new(room_id);
+    acfun.parse().await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_play_res() {
+        let play_res = json!({
+            "liveAdaptiveManifest": [{
+                "adaptationSet": {
+                    "representation": [
+                        {"url": "https://hw.example.com/live/abc_hd.flv", "name": "高清", "bitrate": 2000},
+                        {"url": "https://hw.example.com/live/abc_bd.flv", "name": "蓝光 4M", "bitrate": 4000}
+                    ]
+                }
+            }]
+        });
+        let resp = json!({
+            "result": 1,
+            "data": {"caption": "标题", "videoPlayRes": play_res.to_string()}
+        });
+
+        let (links, qualities) = parse_play_res(&resp).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].quality.as_deref(), Some("蓝光 4M"));
+        assert_eq!(qualities[1].value, 2000);
+
+        assert!(matches!(
+            parse_play_res(&json!({"result": 380023, "error_msg": "主播未开播"})),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+        assert!(parse_play_res(&json!({"result": 129510, "error_msg": "签名错误"})).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE};
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult};
+use crate::network::http::Client;
+
+pub(super) const ID_BASE_URL: &str = "https://id.app.acfun.cn";
+const LOGIN_PATH: &str = "/rest/app/visitor/login";
+
+/// 游客登录的服务 ID，返回的 token 用于 `kuaishouzt.com` 的直播接口
+///
+/// `acfun.midground.api` 的 token 只能由已登录账号通过 `/rest/web/token/get` 获取，
+/// 未登录时只能用游客登录得到的 `acfun.api.visitor_st` 调用 `startPlay`
+const VISITOR_SID: &str = "acfun.api.visitor";
+const VISITOR_TOKEN_KEY: &str = "acfun.api.visitor_st";
+
+/// 游客身份，`did` 需要同时放在 cookie 和 `startPlay` 的参数中
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Visitor {
+    pub did: String,
+    pub user_id: u64,
+    pub token: String,
+}
+
+/// 与网页端生成的 `_did` 格式一致
+pub(super) fn generate_did() -> String {
+    let mut rng = rand::rng();
+    let hex: String = (0..16)
+        .map(|_| format!("{:X}", rng.random_range(0..16u8)))
+        .collect();
+    format!("web_{}", hex)
+}
+
+pub(super) struct VisitorLogin<'a> {
+    client: &'a Client,
+    base_url: &'a str,
+}
+
+impl<'a> VisitorLogin<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self::with_base_url(client, ID_BASE_URL)
+    }
+
+    pub fn with_base_url(client: &'a Client, base_url: &'a str) -> Self {
+        Self { client, base_url }
+    }
+
+    pub async fn login(&self, did: String) -> LsarResult<Visitor> {
+        debug!("Logging in to AcFun as visitor, did: {}", did);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&format!("_did={}", did)).map_err(|e| e.to_string())?,
+        );
+
+        let resp: Value = self
+            .client
+            .post_form(
+                &format!("{}{}", self.base_url, LOGIN_PATH),
+                &[("sid", VISITOR_SID)],
+                headers,
+            )
+            .await?;
+
+        if resp["result"].as_i64() != Some(0) {
+            error!("AcFun visitor login failed: {}", resp);
+            return Err(LsarError::from("AcFun 游客登录失败"));
+        }
+
+        match (resp["userId"].as_u64(), resp[VISITOR_TOKEN_KEY].as_str()) {
+            (Some(user_id), Some(token)) => Ok(Visitor {
+                did,
+                user_id,
+                token: token.to_string(),
+            }),
+            _ => {
+                error!("Unexpected AcFun visitor login response: {}", resp);
+                Err(LsarError::from("AcFun 游客登录失败"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{body_string, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    #[test]
+    fn test_generate_did() {
+        let did = generate_did();
+        assert!(did.starts_with("web_"));
+        assert_eq!(did.len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_login() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(LOGIN_PATH))
+            .and(header("Cookie", "_did=web_0123456789ABCDEF"))
+            .and(body_string("sid=acfun.api.visitor"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "result": 0,
+                "userId": 1000000012345678u64,
+                "acfun.api.visitor_st": "ChRhY2Z1bi5hcGkudmlzaXRvci5zdBJw"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let base_url = server.uri();
+        let visitor = VisitorLogin::with_base_url(&client, &base_url)
+            .login("web_0123456789ABCDEF".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(visitor.user_id, 1000000012345678);
+        assert_eq!(visitor.token, "ChRhY2Z1bi5hcGkudmlzaXRvci5zdBJw");
+    }
+}
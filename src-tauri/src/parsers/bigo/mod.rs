@@ -1,24 +1,48 @@
 use serde::{Deserialize, Serialize};
+use url::Url;
 
-use crate::error::LsarResult;
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
 use crate::network::http::Client;
-use crate::parsers::{ParsedResult, Parser};
+use crate::parsers::{Link, ParsedResult, Parser};
 use crate::platform::Platform;
 
+/// `roomStatus` 为此值时正在直播
+const ROOM_STATUS_LIVE: i8 = 1;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct RoomInfo {
     #[serde(rename = "clientBigoId")]
     client_bigo_id: String,
+    #[serde(default)]
     hls_src: String,
-    #[serde(rename = "roomType")]
+    #[serde(rename = "roomType", default)]
     room_type: String,
-    #[serde(rename = "roomTopic")]
+    #[serde(rename = "roomTopic", default)]
     room_topic: String,
+    /// 游戏直播间的游戏名，其他直播间为空
+    #[serde(rename = "gameTitle", default)]
+    game_title: String,
     nick_name: String,
     #[serde(rename = "roomStatus")]
     room_status: i8,
 }
 
+impl RoomInfo {
+    /// 优先使用游戏名，否则使用房间类型
+    fn category(&self) -> String {
+        if !self.game_title.is_empty() {
+            return self.game_title.clone();
+        }
+
+        match self.room_type.as_str() {
+            "1" => "聊天".to_string(),
+            "2" => "游戏".to_string(),
+            "3" => "多人连麦".to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Response {
     msg: String,
@@ -26,44 +50,133 @@ struct Response {
 }
 
 pub struct BigoParser {
-    room_id: u64,
+    /// 数字 ID 或用户名，接口的 `siteId` 两者都接受
+    site_id: String,
     http_client: Client,
 }
 
 impl BigoParser {
-    fn new(room_id: u64) -> Self {
+    fn new(site_id: String) -> Self {
         Self {
-            room_id,
+            site_id,
             http_client: Client::new(),
         }
     }
 
-    async fn get_real_url(&self) -> LsarResult<RoomInfo> {
+    async fn get_room_info(&self) -> LsarResult<RoomInfo> {
         const URL: &str = "https://ta.bigo.tv/official_website/studio/getInternalStudioInfo";
-        let body = [("siteId", self.room_id)];
+        let body = [("siteId", self.site_id.as_str())];
         let resp: Response = self.http_client.post_form(URL, &body, None).await?;
 
+        debug!(
+            site_id = self.site_id,
+            status = resp.data.room_status,
+            "Got Bigo room info"
+        );
         Ok(resp.data)
     }
 }
 
+fn into_result(info: RoomInfo) -> LsarResult<ParsedResult> {
+    // 未开播的房间同样返回成功，只是 hls_src 为空
+    if info.room_status != ROOM_STATUS_LIVE || info.hls_src.is_empty() {
+        info!("Bigo room {} is offline", info.client_bigo_id);
+        return Err(RoomStateError::Offline.into());
+    }
+
+    Ok(ParsedResult {
+        platform: Platform::Bigo,
+        category: info.category(),
+        links: vec![Link::new(info.hls_src).format("HLS")],
+        room_id: info.client_bigo_id,
+        title: info.room_topic,
+        anchor: info.nick_name,
+        qualities: Vec::new(),
+        guest: false,
+        scheduled_start: None,
+        keep_alive_session: None,
+    })
+}
+
 impl Parser for BigoParser {
     async fn parse(&mut self) -> LsarResult<ParsedResult> {
-        let result = self.get_real_url().await?;
-
-        Ok(ParsedResult {
-            platform: Platform::Bigo,
-            title: result.room_topic,
-            anchor: result.nick_name,
-            room_id: self.room_id,
-            category: "".to_string(),
-            links: vec![result.hls_src],
-        })
+        let info = self.get_room_info().await?;
+        into_result(info)
+    }
+}
+
+/// 从 `bigo.tv/<语言>/<用户名或 ID>` 形式的链接中取出 `siteId`
+fn resolve_site_id(input: &str) -> LsarResult<String> {
+    let url = Url::parse(input)?;
+    let host = url.host_str().unwrap_or_default();
+
+    if host != "bigo.tv" && !host.ends_with(".bigo.tv") {
+        error!("Unsupported Bigo URL: {}", url);
+        return Err(LsarError::Other(format!("不支持的 Bigo 链接: {}", url)));
     }
+
+    // 语言前缀可有可无，用户名总是最后一段
+    url.path_segments()
+        .and_then(|s| s.rev().find(|s| !s.is_empty()))
+        .map(str::to_string)
+        .ok_or(MissKeyFieldError::RoomId.into())
 }
 
+/// `room_id` 和 `url` 二选一
 #[tauri::command]
-pub async fn parse_bigo(room_id: u64) -> LsarResult<ParsedResult> {
-    let mut bigo = BigoParser::new(room_id);
+pub async fn parse_bigo(room_id: Option<u64>, url: String) -> LsarResult<ParsedResult> {
+    let site_id = match room_id {
+        Some(room_id) => room_id.to_string(),
+        None => resolve_site_id(&url)?,
+    };
+    let mut bigo = BigoParser::new(site_id);
     bigo.parse().await
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn room_info(status: i8, hls_src: &str) -> RoomInfo {
+        serde_json::from_value(json!({
+            "clientBigoId": "lisa_live",
+            "hls_src": hls_src,
+            "roomType": "2",
+            "roomTopic": "今晚上分",
+            "gameTitle": "",
+            "nick_name": "Lisa",
+            "roomStatus": status
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_site_id() {
+        assert_eq!(
+            resolve_site_id("https://www.bigo.tv/cn/lisa_live").unwrap(),
+            "lisa_live"
+        );
+        assert_eq!(
+            resolve_site_id("https://www.bigo.tv/52071542?from=share").unwrap(),
+            "52071542"
+        );
+        assert!(resolve_site_id("https://www.bigo.tv/").is_err());
+        assert!(resolve_site_id("https://www.yy.com/52071542").is_err());
+    }
+
+    #[test]
+    fn test_into_result() {
+        let result = into_result(room_info(1, "https://example.com/live.m3u8")).unwrap();
+        assert_eq!(result.room_id, "lisa_live");
+        assert_eq!(result.category, "游戏");
+        assert_eq!(result.links[0].url, "https://example.com/live.m3u8");
+
+        assert!(matches!(
+            into_result(room_info(0, "")),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+        assert!(into_result(room_info(1, "")).is_err());
+    }
+}
@@ -1,20 +1,42 @@
+use std::sync::{LazyLock, Mutex};
+
 use reqwest::Client;
+use tauri::{AppHandle, Emitter};
 
-use crate::error::LsarResult;
+use crate::config::Config;
+use crate::error::{LsarError, LsarResult};
 use crate::parsers::ParsedResult;
 use crate::platform::Platform;
+use crate::utils::now;
 
+use super::cookie_refresher::CookieRefresher;
 use super::cookie_verifier::CookieVerifier;
 use super::html_fetcher::HTMLFetcher;
 use super::link_parser::LinkParser;
+use super::passport::sessdata_expires_at;
 use super::room_info_fetcher::RoomInfoFetcher;
-use super::room_play_info_fetcher::RoomPlayInfoFetcher;
+use super::room_play_info_fetcher::{PlayOptions, RoomPlayInfoFetcher};
+
+/// SESSDATA 剩余有效期不足此秒数时主动刷新
+const REFRESH_BEFORE_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// cookie 已刷新并写入配置文件，前端需要重新读取配置
+pub const COOKIE_REFRESHED_EVENT: &str = "bilibili-cookie-refreshed";
+/// cookie 即将或已经失效且无法自动刷新，需要重新登录
+pub const COOKIE_WARNING_EVENT: &str = "bilibili-cookie-warning";
+
+/// 已提醒过的 cookie，同一个 cookie 只提醒一次，不随验证结果过期重复提醒
+static WARNED_COOKIE: LazyLock<Mutex<Option<String>>> = LazyLock::new(|| Mutex::new(None));
 
 pub struct BilibiliParser {
     room_id: u64,
     page_url: String,
     cookie: String,
+    options: PlayOptions,
+    /// 加密房间的密码
+    password: Option<String>,
     client: Client,
+    app: Option<AppHandle>,
 }
 
 impl BilibiliParser {
@@ -26,24 +48,144 @@ impl BilibiliParser {
             room_id,
             page_url,
             cookie,
+            options: PlayOptions::default(),
+            password: None,
             client,
+            app: None,
         }
     }
 
-    pub async fn parse(&mut self) -> LsarResult<ParsedResult> {
-        trace!("Starting parsing process for room ID: {}", self.room_id);
+    pub fn with_app(mut self, app: AppHandle) -> Self {
+        self.app = Some(app);
+        self
+    }
 
-        let cookie_verifier = CookieVerifier::new(&self.client, &self.cookie);
-        match cookie_verifier.verify().await {
-            Ok(username) => {
-                info!(
-                    "Cookie verification successful. Logged in user: {}",
-                    username
-                );
+    fn emit<S: serde::Serialize + Clone>(&self, event: &str, payload: S) {
+        if let Some(app) = &self.app {
+            if let Err(e) = app.emit(event, payload) {
+                error!("Failed to emit {}: {}", event, e);
+            }
+        }
+    }
+
+    fn warn_cookie(&self, message: String) {
+        if let Ok(mut warned) = WARNED_COOKIE.lock() {
+            if warned.as_deref() == Some(self.cookie.as_str()) {
+                debug!("Cookie warning already emitted: {}", message);
+                return;
             }
+            *warned = Some(self.cookie.clone());
+        }
+        self.emit(COOKIE_WARNING_EVENT, message);
+    }
+
+    /// 验证 cookie，SESSDATA 即将过期或验证失败时才检查是否需要刷新，需要时用 refresh_token 刷新
+    async fn ensure_cookie(&mut self) -> LsarResult<String> {
+        if let Some(username) = CookieVerifier::new(&self.client, &self.cookie).cached() {
+            debug!("Cookie verified recently. Logged in user: {}", username);
+            return Ok(username);
+        }
+
+        let expiring = self.sessdata_expiring();
+        if expiring {
+            self.try_refresh_cookie(true).await;
+        }
+
+        let mut verified = CookieVerifier::new(&self.client, &self.cookie)
+            .verify()
+            .await;
+        // 未到期的 cookie 验证失败时，可能是服务端要求刷新
+        if verified.is_err() && !expiring && self.try_refresh_cookie(false).await {
+            verified = CookieVerifier::new(&self.client, &self.cookie)
+                .verify()
+                .await;
+        }
+
+        verified.inspect_err(|_| self.warn_cookie("B 站 cookie 已失效，请重新扫码登录".to_string()))
+    }
+
+    fn sessdata_expiring(&self) -> bool {
+        let expires_at = sessdata_expires_at(&self.cookie);
+        let expiring =
+            expires_at.is_some_and(|t| t <= now().map_or(0, |n| n.as_secs()) + REFRESH_BEFORE_SECS);
+        debug!(
+            "SESSDATA expires at: {:?}, expiring: {}",
+            expires_at, expiring
+        );
+        expiring
+    }
+
+    /// 返回 cookie 是否已刷新，刷新失败时提醒重新登录
+    async fn try_refresh_cookie(&mut self, force: bool) -> bool {
+        match self.refresh_cookie(force).await {
+            Ok(refreshed) => refreshed,
             Err(e) => {
-                error!("Cookie verification failed. Error: {}. Details: {:?}", e, e);
-                return Err(e);
+                warn!("Failed to refresh cookie: {}", e);
+                self.warn_cookie(format!("B 站 cookie 刷新失败，请重新扫码登录：{}", e));
+                false
+            }
+        }
+    }
+
+    /// `force` 为 false 时仅在接口要求刷新时刷新
+    async fn refresh_cookie(&mut self, force: bool) -> LsarResult<bool> {
+        let refresher = CookieRefresher::new(&self.client);
+        let info = refresher.cookie_info(&self.cookie).await?;
+        if !info.refresh && !force {
+            return Ok(false);
+        }
+
+        let config = Config::read_from_file()?;
+        let refresh_token = config.bilibili_refresh_token();
+        if refresh_token.is_empty() {
+            return Err(LsarError::from("没有 refresh_token，无法自动刷新"));
+        }
+
+        let credential = refresher
+            .refresh(&self.cookie, refresh_token, info.timestamp)
+            .await?;
+        Config::save_bilibili_credential(credential.cookie.clone(), credential.refresh_token)
+            .await?;
+        self.cookie = credential.cookie;
+        self.emit(COOKIE_REFRESHED_EVENT, ());
+
+        Ok(true)
+    }
+
+    pub fn with_options(mut self, options: PlayOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_password(mut self, password: Option<String>) -> Self {
+        self.password = password.filter(|p| !p.is_empty());
+        self
+    }
+
+    pub async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        trace!("Starting parsing process for room ID: {}", self.room_id);
+
+        // 未登录时仍可匿名获取较低画质的直播流
+        let guest = if self.cookie.is_empty() {
+            info!("No cookie provided, parsing as guest");
+            true
+        } else {
+            match self.ensure_cookie().await {
+                Ok(username) => {
+                    info!(
+                        "Cookie verification successful. Logged in user: {}",
+                        username
+                    );
+                    false
+                }
+                Err(e) => {
+                    warn!(
+                        "Cookie verification failed, falling back to guest mode. Error: {}",
+                        e
+                    );
+                    self.cookie.clear();
+                    true
+                }
             }
         };
 
@@ -66,40 +208,51 @@ impl BilibiliParser {
             self.room_id = self.parse_room_id(&html)?;
         }
 
-        let room_info_fetcher = RoomInfoFetcher::new(&self.client, self.room_id, &self.cookie);
-        let page_info = match room_info_fetcher.fetch().await {
+        // 先获取直播流，锁定、加密、付费等房间状态由此接口判断
+        let room_play_info_fetcher = RoomPlayInfoFetcher::new(
+            &self.client,
+            self.room_id,
+            &self.cookie,
+            self.options,
+            self.password.as_deref(),
+        );
+        let room_play_info = match room_play_info_fetcher.fetch().await {
             Ok(info) => {
-                debug!(
-                    "Fetched room info successfully. Title: {}, Anchor: {}, Category: {}",
-                    info.0, info.1, info.2
-                );
+                debug!("Fetched room play info successfully");
                 info
             }
             Err(e) => {
-                error!("Failed to fetch room info. Error: {}. Details: {:?}", e, e);
+                error!(
+                    "Failed to fetch room play info. Error: {}. Details: {:?}",
+                    e, e
+                );
                 return Err(e);
             }
         };
 
-        let room_play_info_fetcher =
-            RoomPlayInfoFetcher::new(&self.client, self.room_id, &self.cookie);
-        let room_play_info = match room_play_info_fetcher.fetch().await {
+        let room_info_fetcher = RoomInfoFetcher::new(&self.client, self.room_id, &self.cookie);
+        let page_info = match room_info_fetcher.fetch().await {
             Ok(info) => {
-                debug!("Fetched room play info successfully");
+                debug!(
+                    "Fetched room info successfully. Title: {}, Anchor: {}, Category: {}",
+                    info.0, info.1, info.2
+                );
                 info
             }
             Err(e) => {
-                error!(
-                    "Failed to fetch room play info. Error: {}. Details: {:?}",
-                    e, e
-                );
+                error!("Failed to fetch room info. Error: {}. Details: {:?}", e, e);
                 return Err(e);
             }
         };
 
-        let link_parser = LinkParser::new();
+        let link_parser = LinkParser::new(&self.options);
         let links = link_parser.parse(&room_play_info);
-        debug!("Parsed {} stream links", links.len());
+        let qualities = link_parser.qualities(&room_play_info);
+        debug!(
+            "Parsed {} stream links, {} qualities",
+            links.len(),
+            qualities.len()
+        );
 
         let parsed_result = ParsedResult {
             title: page_info.0,
@@ -107,7 +260,11 @@ impl BilibiliParser {
             category: page_info.2,
             platform: Platform::Bilibili,
             links,
-            room_id: self.room_id,
+            qualities,
+            guest,
+            scheduled_start: None,
+            keep_alive_session: None,
+            room_id: self.room_id.to_string(),
         };
 
         info!(
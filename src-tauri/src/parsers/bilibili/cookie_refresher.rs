@@ -0,0 +1,358 @@
+use regex::Regex;
+use reqwest::Client;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::rand_core::OsRng;
+use rsa::sha2::Sha256;
+use rsa::{Oaep, RsaPublicKey};
+use serde::Deserialize;
+
+use crate::error::{LsarError, LsarResult};
+
+use super::passport::{
+    build_cookie, cookie_value, set_cookies, Credential, Response, PASSPORT_BASE_URL,
+};
+
+const CORRESPOND_BASE_URL: &str = "https://www.bilibili.com";
+const COOKIE_INFO_PATH: &str = "/x/passport-login/web/cookie/info";
+const COOKIE_REFRESH_PATH: &str = "/x/passport-login/web/cookie/refresh";
+const CONFIRM_REFRESH_PATH: &str = "/x/passport-login/web/confirm/refresh";
+const CORRESPOND_PATH: &str = "/correspond/1/";
+
+/// 生成 correspondPath 所用的公钥
+const PUBLIC_KEY_PEM: &str = "-----BEGIN PUBLIC KEY-----
+MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQDLgd2OAkcGVtoE3ThUREbio0Eg
+Uc/prcajMKXvkCKFCWhJYJcLkcM2DKKcSeFpD/j6Boy538YXnR6VhcuUJOhH2x71
+nzPjfdTcqMz7djHum0qSZA0AyCBDABUqCrfNgCiJ00Ra7GmRj+YCK1NJEuewlb40
+JNrRuoEUXpabUzGB8QIDAQAB
+-----END PUBLIC KEY-----";
+
+#[derive(Debug, Deserialize)]
+pub struct CookieInfo {
+    /// 是否需要刷新
+    pub refresh: bool,
+    /// 毫秒时间戳，用于生成 correspondPath
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RefreshData {
+    refresh_token: String,
+}
+
+/// 使用 refresh_token 刷新 cookie
+///
+/// 流程：查询是否需要刷新 → 用公钥加密时间戳得到 correspondPath → 从 correspond 页面取得
+/// refresh_csrf → 刷新 cookie → 用新 cookie 确认刷新，使旧 refresh_token 失效。
+pub struct CookieRefresher<'a> {
+    client: &'a Client,
+    passport_base_url: &'a str,
+    correspond_base_url: &'a str,
+}
+
+impl<'a> CookieRefresher<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self::with_base_urls(client, PASSPORT_BASE_URL, CORRESPOND_BASE_URL)
+    }
+
+    pub fn with_base_urls(
+        client: &'a Client,
+        passport_base_url: &'a str,
+        correspond_base_url: &'a str,
+    ) -> Self {
+        CookieRefresher {
+            client,
+            passport_base_url,
+            correspond_base_url,
+        }
+    }
+
+    pub async fn cookie_info(&self, cookie: &str) -> LsarResult<CookieInfo> {
+        debug!("Checking whether Bilibili cookie needs refresh");
+        let csrf = cookie_value(cookie, "bili_jct").unwrap_or_default();
+        let mut url = url::Url::parse(&format!("{}{}", self.passport_base_url, COOKIE_INFO_PATH))?;
+        url.query_pairs_mut().append_pair("csrf", csrf);
+
+        let response: Response<CookieInfo> = self
+            .client
+            .get(url)
+            .header("Cookie", cookie)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Failed to request cookie info: {}", e);
+                LsarError::from(e.to_string())
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                error!("Failed to deserialize cookie info: {}", e);
+                LsarError::from(e.to_string())
+            })?;
+
+        if response.code != 0 {
+            error!(
+                "Cookie info request failed ({}): {}",
+                response.code, response.message
+            );
+            return Err(LsarError::Other(response.message));
+        }
+
+        response
+            .data
+            .ok_or_else(|| LsarError::from("cookie 信息为空"))
+    }
+
+    pub async fn refresh(
+        &self,
+        cookie: &str,
+        refresh_token: &str,
+        timestamp: u64,
+    ) -> LsarResult<Credential> {
+        info!("Refreshing Bilibili cookie");
+        let csrf = cookie_value(cookie, "bili_jct").ok_or_else(|| {
+            error!("bili_jct not found in cookie");
+            LsarError::from("cookie 中缺少 bili_jct")
+        })?;
+
+        let refresh_csrf = self.fetch_refresh_csrf(cookie, timestamp).await?;
+
+        let resp = self
+            .client
+            .post(format!("{}{}", self.passport_base_url, COOKIE_REFRESH_PATH))
+            .header("Cookie", cookie)
+            .form(&[
+                ("csrf", csrf),
+                ("refresh_csrf", refresh_csrf.as_str()),
+                ("source", "main_web"),
+                ("refresh_token", refresh_token),
+            ])
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Failed to refresh cookie: {}", e);
+                LsarError::from(e.to_string())
+            })?;
+
+        let set_cookies = set_cookies(resp.headers());
+        let response: Response<RefreshData> = resp.json().await.map_err(|e| {
+            error!("Failed to deserialize refresh response: {}", e);
+            LsarError::from(e.to_string())
+        })?;
+
+        if response.code != 0 {
+            // 86095: refresh_csrf 错误或 refresh_token 与 cookie 不匹配
+            error!(
+                "Cookie refresh failed ({}): {}",
+                response.code, response.message
+            );
+            return Err(LsarError::Other(response.message));
+        }
+
+        let data = response
+            .data
+            .ok_or_else(|| LsarError::from("刷新 cookie 的响应数据为空"))?;
+        let credential = Credential {
+            cookie: build_cookie(&set_cookies, "")?,
+            refresh_token: data.refresh_token,
+        };
+
+        // 确认失败不影响新 cookie 的使用，只是旧 refresh_token 暂时不会失效
+        if let Err(e) = self
+            .confirm_refresh(&credential.cookie, refresh_token)
+            .await
+        {
+            warn!("Failed to confirm cookie refresh: {}", e);
+        }
+
+        info!("Bilibili cookie refreshed");
+        Ok(credential)
+    }
+
+    async fn fetch_refresh_csrf(&self, cookie: &str, timestamp: u64) -> LsarResult<String> {
+        let url = format!(
+            "{}{}{}",
+            self.correspond_base_url,
+            CORRESPOND_PATH,
+            correspond_path(timestamp)?
+        );
+        debug!("Fetching refresh_csrf");
+
+        let html = self
+            .client
+            .get(url)
+            .header("Cookie", cookie)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Failed to request correspond page: {}", e);
+                LsarError::from(e.to_string())
+            })?
+            .text()
+            .await
+            .map_err(|e| {
+                error!("Failed to read correspond page: {}", e);
+                LsarError::from(e.to_string())
+            })?;
+
+        extract_refresh_csrf(&html)
+    }
+
+    async fn confirm_refresh(&self, cookie: &str, old_refresh_token: &str) -> LsarResult<()> {
+        let csrf = cookie_value(cookie, "bili_jct").unwrap_or_default();
+        let response: Response<serde_json::Value> = self
+            .client
+            .post(format!(
+                "{}{}",
+                self.passport_base_url, CONFIRM_REFRESH_PATH
+            ))
+            .header("Cookie", cookie)
+            .form(&[("csrf", csrf), ("refresh_token", old_refresh_token)])
+            .send()
+            .await
+            .map_err(|e| LsarError::from(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| LsarError::from(e.to_string()))?;
+
+        if response.code != 0 {
+            return Err(LsarError::Other(response.message));
+        }
+
+        Ok(())
+    }
+}
+
+/// 用 RSA-OAEP(SHA-256) 加密 `refresh_<毫秒时间戳>`，结果为小写十六进制
+fn correspond_path(timestamp: u64) -> LsarResult<String> {
+    let key = RsaPublicKey::from_public_key_pem(PUBLIC_KEY_PEM).map_err(|e| {
+        error!("Failed to load public key: {}", e);
+        LsarError::Other(e.to_string())
+    })?;
+
+    let data = format!("refresh_{}", timestamp);
+    let encrypted = key
+        .encrypt(&mut OsRng, Oaep::new::<Sha256>(), data.as_bytes())
+        .map_err(|e| {
+            error!("Failed to encrypt correspond path: {}", e);
+            LsarError::Other(e.to_string())
+        })?;
+
+    Ok(encrypted.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn extract_refresh_csrf(html: &str) -> LsarResult<String> {
+    let re = Regex::new(r#"<div id="1-name">([^<]+)</div>"#)?;
+    let refresh_csrf = re
+        .captures(html)
+        .map(|caps| caps[1].trim().to_string())
+        .ok_or_else(|| {
+            error!("refresh_csrf not found in correspond page");
+            LsarError::from("获取 refresh_csrf 失败")
+        })?;
+
+    Ok(refresh_csrf)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, header, method, path, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    const COOKIE: &str = "SESSDATA=old%2C1700000000%2Cx; bili_jct=oldcsrf; DedeUserID=1";
+
+    #[test]
+    fn test_correspond_path() {
+        let path = correspond_path(1700000000000).unwrap();
+
+        // 1024 位公钥，密文 128 字节
+        assert_eq!(path.len(), 256);
+        assert!(path
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+        // OAEP 带随机填充，每次结果不同
+        assert_ne!(path, correspond_path(1700000000000).unwrap());
+    }
+
+    #[test]
+    fn test_extract_refresh_csrf() {
+        let html =
+            r#"<html><body><div id="1-name">b0cc8411ded2f9db2cff2edb3123acac</div></body></html>"#;
+        assert_eq!(
+            extract_refresh_csrf(html).unwrap(),
+            "b0cc8411ded2f9db2cff2edb3123acac"
+        );
+        assert!(extract_refresh_csrf("<html></html>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refresh() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(COOKIE_INFO_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "code": 0,
+                "message": "0",
+                "data": {"refresh": true, "timestamp": 1700000000000u64}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/correspond/1/[0-9a-f]{256}$"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"<div id="1-name">rcsrf</div>"#),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(COOKIE_REFRESH_PATH))
+            .and(body_string_contains("refresh_csrf=rcsrf"))
+            .and(body_string_contains("refresh_token=oldtoken"))
+            .and(body_string_contains("csrf=oldcsrf"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .append_header("Set-Cookie", "SESSDATA=new%2C1800000000%2Cy; Path=/")
+                    .append_header("Set-Cookie", "bili_jct=newcsrf; Path=/")
+                    .append_header("Set-Cookie", "DedeUserID=1; Path=/")
+                    .set_body_json(json!({
+                        "code": 0,
+                        "message": "0",
+                        "data": {"status": 0, "message": "", "refresh_token": "newtoken"}
+                    })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(CONFIRM_REFRESH_PATH))
+            .and(header(
+                "Cookie",
+                "SESSDATA=new%2C1800000000%2Cy; bili_jct=newcsrf; DedeUserID=1",
+            ))
+            .and(body_string_contains("refresh_token=oldtoken"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"code": 0, "message": "0"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let uri = server.uri();
+        let refresher = CookieRefresher::with_base_urls(&client, &uri, &uri);
+
+        let info = refresher.cookie_info(COOKIE).await.unwrap();
+        assert!(info.refresh);
+
+        let credential = refresher
+            .refresh(COOKIE, "oldtoken", info.timestamp)
+            .await
+            .unwrap();
+        assert_eq!(
+            credential.cookie,
+            "SESSDATA=new%2C1800000000%2Cy; bili_jct=newcsrf; DedeUserID=1"
+        );
+        assert_eq!(credential.refresh_token, "newtoken");
+    }
+}
@@ -1,10 +1,24 @@
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::Value;
 
 use crate::error::{LsarError, LsarResult};
 
-const VERIFY_URL: &str = "https://api.bilibili.com/x/web-interface/nav";
+use super::wbi::{WbiSigner, NAV_URL};
+
+/// 验证结果的有效期，期间不再请求接口
+const VERIFY_TTL: Duration = Duration::from_secs(10 * 60);
+
+struct Verified {
+    cookie: String,
+    username: String,
+    verified_at: Instant,
+}
+
+static VERIFIED: LazyLock<Mutex<Option<Verified>>> = LazyLock::new(|| Mutex::new(None));
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all(deserialize = "camelCase"))]
@@ -29,12 +43,36 @@ impl<'a> CookieVerifier<'a> {
         CookieVerifier { client, cookie }
     }
 
+    /// 有效期内已验证过的 cookie 直接返回用户名
+    pub fn cached(&self) -> Option<String> {
+        let verified = VERIFIED.lock().ok()?;
+        verified
+            .as_ref()
+            .filter(|v| v.cookie == self.cookie && v.verified_at.elapsed() < VERIFY_TTL)
+            .map(|v| v.username.clone())
+    }
+
+    fn store(&self, username: &str) {
+        if let Ok(mut verified) = VERIFIED.lock() {
+            *verified = Some(Verified {
+                cookie: self.cookie.to_owned(),
+                username: username.to_owned(),
+                verified_at: Instant::now(),
+            });
+        }
+    }
+
     pub async fn verify(&self) -> LsarResult<String> {
+        if let Some(username) = self.cached() {
+            debug!("Using cached cookie verification for user: {}", username);
+            return Ok(username);
+        }
+
         debug!("Starting cookie verification process");
 
         let response_value = self
             .client
-            .get(VERIFY_URL)
+            .get(NAV_URL)
             .header("Cookie", self.cookie)
             .send()
             .await
@@ -51,6 +89,9 @@ impl<'a> CookieVerifier<'a> {
 
         debug!("Cookie verification result: {}", response_value);
 
+        // 顺便缓存 WBI 密钥，之后签名时不用再请求
+        WbiSigner::from_nav(&response_value);
+
         let response: VerifyResponse = serde_json::from_value(response_value)?;
 
         if response.code != 0 {
@@ -72,6 +113,7 @@ impl<'a> CookieVerifier<'a> {
         })?;
 
         debug!("Cookie verification successful for user: {}", username);
+        self.store(&username);
         Ok(username)
     }
 }
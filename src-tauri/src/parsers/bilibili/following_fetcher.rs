@@ -0,0 +1,168 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LsarError, LsarResult};
+
+use super::passport::Response;
+
+const FOLLOWING_URL: &str = "https://api.live.bilibili.com/xlive/web-ucenter/user/following";
+const PAGE_SIZE: u32 = 10;
+/// 防止接口返回异常的总页数时无限翻页
+const MAX_PAGES: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+struct FollowingItem {
+    roomid: u64,
+    uid: u64,
+    uname: String,
+    #[serde(default)]
+    title: String,
+    /// 1 为直播中
+    live_status: i32,
+    #[serde(default)]
+    area_name_v2: String,
+    #[serde(default)]
+    room_cover: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FollowingPage {
+    #[serde(default)]
+    list: Vec<FollowingItem>,
+    #[serde(rename = "totalPage", default)]
+    total_page: u32,
+}
+
+/// 正在直播的关注主播
+#[derive(Debug, Serialize)]
+pub struct FollowedRoom {
+    pub room_id: u64,
+    pub uid: u64,
+    pub anchor: String,
+    pub title: String,
+    pub category: String,
+    pub cover: String,
+}
+
+impl From<FollowingItem> for FollowedRoom {
+    fn from(item: FollowingItem) -> Self {
+        FollowedRoom {
+            room_id: item.roomid,
+            uid: item.uid,
+            anchor: item.uname,
+            title: item.title,
+            category: item.area_name_v2,
+            cover: item.room_cover,
+        }
+    }
+}
+
+pub struct FollowingFetcher<'a> {
+    client: &'a Client,
+    cookie: &'a str,
+}
+
+impl<'a> FollowingFetcher<'a> {
+    pub fn new(client: &'a Client, cookie: &'a str) -> Self {
+        FollowingFetcher { client, cookie }
+    }
+
+    /// 逐页获取关注的直播间，只返回正在直播的
+    pub async fn fetch_live(&self) -> LsarResult<Vec<FollowedRoom>> {
+        let mut rooms = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let following = self.fetch_page(page).await?;
+            let total_page = following.total_page.min(MAX_PAGES);
+            debug!(
+                "Fetched following page {}/{}, {} rooms",
+                page,
+                total_page,
+                following.list.len()
+            );
+
+            rooms.extend(live_rooms(following.list));
+
+            if page >= total_page {
+                break;
+            }
+            page += 1;
+        }
+
+        info!("Found {} followed rooms that are live", rooms.len());
+        Ok(rooms)
+    }
+
+    async fn fetch_page(&self, page: u32) -> LsarResult<FollowingPage> {
+        let url = format!(
+            "{}?page={}&page_size={}&ignoreRecord=1&hit_ab=true",
+            FOLLOWING_URL, page, PAGE_SIZE
+        );
+
+        let response: Response<FollowingPage> = self
+            .client
+            .get(url)
+            .header("Cookie", self.cookie)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Failed to request following list: {}", e);
+                LsarError::from(e.to_string())
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                error!("Failed to deserialize following list: {}", e);
+                LsarError::from(e.to_string())
+            })?;
+
+        if response.code != 0 {
+            // -101: 账号未登录
+            error!(
+                "Following list request failed ({}): {}",
+                response.code, response.message
+            );
+            return Err(LsarError::Other(response.message));
+        }
+
+        response.data.ok_or_else(|| LsarError::from("关注列表为空"))
+    }
+}
+
+fn live_rooms(list: Vec<FollowingItem>) -> impl Iterator<Item = FollowedRoom> {
+    list.into_iter()
+        .filter(|item| item.live_status == 1)
+        .map(FollowedRoom::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_live_rooms() {
+        let page: FollowingPage = serde_json::from_value(json!({
+            "title": "我的关注",
+            "pageSize": 10,
+            "totalPage": 2,
+            "list": [
+                {"roomid": 6, "uid": 9617619, "uname": "哔哩哔哩英雄联盟赛事", "title": "LPL", "live_status": 1,
+                 "area_name_v2": "英雄联盟", "room_cover": "https://i0.hdslb.com/bfs/live/cover.jpg", "face": ""},
+                {"roomid": 1017, "uid": 1, "uname": "未开播", "title": "", "live_status": 0}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(page.total_page, 2);
+
+        let rooms: Vec<FollowedRoom> = live_rooms(page.list).collect();
+        assert_eq!(rooms.len(), 1);
+        assert_eq!(rooms[0].room_id, 6);
+        assert_eq!(rooms[0].anchor, "哔哩哔哩英雄联盟赛事");
+        assert_eq!(rooms[0].category, "英雄联盟");
+        assert_eq!(rooms[0].cover, "https://i0.hdslb.com/bfs/live/cover.jpg");
+    }
+}
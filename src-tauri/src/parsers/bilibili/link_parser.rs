@@ -1,30 +1,68 @@
-use super::room_play_info_fetcher::Response;
+use crate::parsers::{Link, Quality};
 
-pub struct LinkParser;
+use super::room_play_info_fetcher::{PlayOptions, Response};
 
-impl LinkParser {
-    pub fn new() -> Self {
-        LinkParser
+pub struct LinkParser<'a> {
+    options: &'a PlayOptions,
+}
+
+impl<'a> LinkParser<'a> {
+    pub fn new(options: &'a PlayOptions) -> Self {
+        LinkParser { options }
     }
 
-    pub fn parse(&self, info: &Response) -> Vec<String> {
+    /// 展开 stream → format → codec → url_info，并为每个链接标注画质、CDN、格式和编码。
+    ///
+    /// 接口可能忽略请求中的协议、格式和编码，这里再按选项过滤一次。
+    pub fn parse(&self, info: &Response) -> Vec<Link> {
         trace!("Starting to parse stream links");
+        let playurl = &info.data.playurl_info.playurl;
         let mut links = Vec::new();
 
-        for (stream_index, stream) in info.data.playurl_info.playurl.stream.iter().enumerate() {
-            for (format_index, format) in stream.format.iter().enumerate() {
-                for (codec_index, codec) in format.codec.iter().enumerate() {
-                    for (url_index, url_info) in codec.url_info.iter().enumerate() {
-                        let link = format!("{}{}{}", url_info.host, codec.base_url, url_info.extra);
+        for stream in playurl.stream.iter().filter(|s| {
+            self.options
+                .protocol
+                .is_none_or(|p| p.name() == s.protocol_name)
+        }) {
+            for format in stream.format.iter().filter(|f| {
+                self.options
+                    .format
+                    .is_none_or(|fmt| fmt.name() == f.format_name)
+            }) {
+                for codec in format.codec.iter().filter(|c| {
+                    self.options
+                        .codec
+                        .is_none_or(|codec| codec.name() == c.codec_name)
+                }) {
+                    let quality = playurl
+                        .g_qn_desc
+                        .iter()
+                        .find(|d| d.qn == codec.current_qn)
+                        .map_or_else(|| codec.current_qn.to_string(), |d| d.desc.clone());
+
+                    for url_info in &codec.url_info {
+                        let url = format!("{}{}{}", url_info.host, codec.base_url, url_info.extra);
                         trace!(
-                            "Parsed link: {} (Stream: {}, Format: {}, Codec: {}, URL: {})",
-                            link,
-                            stream_index,
-                            format_index,
-                            codec_index,
-                            url_index
+                            "Parsed link: {} (Protocol: {}, Format: {}, Codec: {}, Qn: {})",
+                            url,
+                            stream.protocol_name,
+                            format.format_name,
+                            codec.codec_name,
+                            codec.current_qn
+                        );
+
+                        let cdn = url_info
+                            .host
+                            .split_once("://")
+                            .map_or(url_info.host.as_str(), |(_, host)| host);
+
+                        links.push(
+                            Link::new(url)
+                                .quality(quality.as_str())
+                                .cdn(cdn)
+                                .format(format_label(&stream.protocol_name, &format.format_name))
+                                .codec(codec.codec_name.to_uppercase()),
                         );
-                        links.push(link);
                     }
                 }
             }
@@ -33,4 +71,135 @@ impl LinkParser {
         debug!("Parsed {} stream links", links.len());
         links
     }
+
+    /// 可选画质，按 `g_qn_desc` 的顺序，只保留各编码 `accept_qn` 中出现过的画质
+    pub fn qualities(&self, info: &Response) -> Vec<Quality> {
+        let playurl = &info.data.playurl_info.playurl;
+        let accept_qn: Vec<u32> = playurl
+            .stream
+            .iter()
+            .flat_map(|s| &s.format)
+            .flat_map(|f| &f.codec)
+            .flat_map(|c| c.accept_qn.iter().copied())
+            .collect();
+
+        playurl
+            .g_qn_desc
+            .iter()
+            .filter(|d| accept_qn.contains(&d.qn))
+            .map(|d| Quality::new(d.qn as i64, d.desc.as_str()))
+            .collect()
+    }
+}
+
+fn format_label(protocol_name: &str, format_name: &str) -> String {
+    match (protocol_name, format_name) {
+        ("http_stream", "flv") => "FLV".to_string(),
+        ("http_hls", "ts") => "HLS-TS".to_string(),
+        ("http_hls", "fmp4") => "HLS-fMP4".to_string(),
+        _ => format!("{}/{}", protocol_name, format_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::room_play_info_fetcher::{Codec, Protocol};
+    use super::*;
+
+    fn response() -> Response {
+        serde_json::from_value(json!({
+            "code": 0,
+            "message": "0",
+            "data": {
+                "live_status": 1,
+                "playurl_info": {
+                    "playurl": {
+                        "g_qn_desc": [
+                            {"qn": 30000, "desc": "杜比"},
+                            {"qn": 10000, "desc": "原画"},
+                            {"qn": 400, "desc": "蓝光"},
+                            {"qn": 250, "desc": "超清"}
+                        ],
+                        "stream": [
+                            {
+                                "protocol_name": "http_stream",
+                                "format": [{
+                                    "format_name": "flv",
+                                    "codec": [{
+                                        "codec_name": "avc",
+                                        "current_qn": 10000,
+                                        "accept_qn": [10000, 400, 250],
+                                        "base_url": "/live-bvc/1/live_1.flv?",
+                                        "url_info": [{"host": "https://cn-gotcha01.bilivideo.com", "extra": "expires=1"}]
+                                    }]
+                                }]
+                            },
+                            {
+                                "protocol_name": "http_hls",
+                                "format": [{
+                                    "format_name": "fmp4",
+                                    "codec": [{
+                                        "codec_name": "hevc",
+                                        "current_qn": 10000,
+                                        "accept_qn": [10000, 400],
+                                        "base_url": "/live-bvc/1/live_1/index.m3u8?",
+                                        "url_info": [{"host": "https://cn-gotcha02.bilivideo.com", "extra": "expires=2"}]
+                                    }]
+                                }]
+                            }
+                        ]
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_labels_links() {
+        let options = PlayOptions::default();
+        let links = LinkParser::new(&options).parse(&response());
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(
+            links[0].url,
+            "https://cn-gotcha01.bilivideo.com/live-bvc/1/live_1.flv?expires=1"
+        );
+        assert_eq!(links[0].quality.as_deref(), Some("原画"));
+        assert_eq!(links[0].cdn.as_deref(), Some("cn-gotcha01.bilivideo.com"));
+        assert_eq!(links[0].format.as_deref(), Some("FLV"));
+        assert_eq!(links[0].codec.as_deref(), Some("AVC"));
+        assert_eq!(links[1].format.as_deref(), Some("HLS-fMP4"));
+        assert_eq!(links[1].codec.as_deref(), Some("HEVC"));
+    }
+
+    #[test]
+    fn test_parse_filters_by_options() {
+        let options = PlayOptions {
+            protocol: Some(Protocol::HttpHls),
+            codec: Some(Codec::Avc),
+            ..Default::default()
+        };
+        assert!(LinkParser::new(&options).parse(&response()).is_empty());
+
+        let options = PlayOptions {
+            codec: Some(Codec::Hevc),
+            ..Default::default()
+        };
+        let links = LinkParser::new(&options).parse(&response());
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].format.as_deref(), Some("HLS-fMP4"));
+    }
+
+    #[test]
+    fn test_qualities() {
+        let options = PlayOptions::default();
+        let qualities = LinkParser::new(&options).qualities(&response());
+        let values: Vec<i64> = qualities.iter().map(|q| q.value).collect();
+
+        assert_eq!(values, vec![10000, 400, 250]);
+        assert_eq!(qualities[0].name, "原画");
+    }
 }
@@ -1,23 +1,43 @@
-use bilibili_parser::BilibiliParser;
+use bilibili_parser::{BilibiliParser, COOKIE_REFRESHED_EVENT};
+use following_fetcher::{FollowedRoom, FollowingFetcher};
+use qrcode_login::{PollResult, QrcodeInfo, QrcodeLogin};
+use room_play_info_fetcher::PlayOptions;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
 
+use crate::config::Config;
+use crate::data::db::import_histories;
+use crate::data::history::HistoryItem;
 use crate::error::LsarResult;
+use crate::platform::Platform;
 
 use super::ParsedResult;
 
 mod bilibili_parser;
+mod cookie_refresher;
 mod cookie_verifier;
+mod following_fetcher;
 mod html_fetcher;
 mod link_parser;
+mod passport;
+mod qrcode_login;
 mod room_info_fetcher;
 mod room_play_info_fetcher;
+mod wbi;
 
 #[tauri::command]
 pub async fn parse_bilibili(
+    app: AppHandle,
     room_id: u64,
     cookie: String,
     url: Option<String>,
+    options: Option<PlayOptions>,
+    password: Option<String>,
 ) -> LsarResult<ParsedResult> {
-    let mut parser = BilibiliParser::new(cookie, room_id, url);
+    let mut parser = BilibiliParser::new(cookie, room_id, url)
+        .with_options(options.unwrap_or_default())
+        .with_password(password)
+        .with_app(app);
 
     match parser.parse().await {
         Ok(result) => {
@@ -30,3 +50,76 @@ pub async fn parse_bilibili(
         }
     }
 }
+
+/// 扫码登录状态
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrcodeStatus {
+    Waiting,
+    Scanned,
+    Expired,
+    /// 登录成功，cookie 已写入配置文件
+    Success,
+}
+
+/// 申请登录二维码，返回二维码内容和用于轮询的 `qrcode_key`
+#[tauri::command]
+pub async fn generate_bilibili_qrcode() -> LsarResult<QrcodeInfo> {
+    let client = reqwest::Client::new();
+    QrcodeLogin::new(&client).generate().await
+}
+
+/// 查询扫码状态，登录成功时将 cookie 和 refresh_token 保存到配置文件，并通知前端重新读取配置
+#[tauri::command]
+pub async fn poll_bilibili_qrcode(app: AppHandle, qrcode_key: String) -> LsarResult<QrcodeStatus> {
+    let client = reqwest::Client::new();
+    let status = match QrcodeLogin::new(&client).poll(&qrcode_key).await? {
+        PollResult::Waiting => QrcodeStatus::Waiting,
+        PollResult::Scanned => QrcodeStatus::Scanned,
+        PollResult::Expired => QrcodeStatus::Expired,
+        PollResult::Success(credential) => {
+            Config::save_bilibili_credential(credential.cookie, credential.refresh_token).await?;
+            if let Err(e) = app.emit(COOKIE_REFRESHED_EVENT, ()) {
+                error!("Failed to emit {}: {}", COOKIE_REFRESHED_EVENT, e);
+            }
+            QrcodeStatus::Success
+        }
+    };
+
+    debug!("Bilibili QR code status: {:?}", status);
+    Ok(status)
+}
+
+/// 获取账号关注的正在直播的主播，`add_to_history` 为 true 时同时导入到历史记录
+///
+/// 本地只有历史记录，没有收藏夹，因此只能导入到历史记录。
+#[tauri::command]
+pub async fn get_bilibili_followed_live_rooms(
+    cookie: String,
+    add_to_history: bool,
+) -> LsarResult<Vec<FollowedRoom>> {
+    let client = reqwest::Client::new();
+    let rooms = FollowingFetcher::new(&client, &cookie).fetch_live().await?;
+
+    if add_to_history && !rooms.is_empty() {
+        let now = time::OffsetDateTime::now_utc();
+        let items: Vec<HistoryItem> = rooms
+            .iter()
+            .map(|room| {
+                HistoryItem::new(
+                    0,
+                    Platform::Bilibili,
+                    room.room_id.to_string(),
+                    room.anchor.clone(),
+                    room.category.clone(),
+                    room.title.clone(),
+                    now,
+                )
+            })
+            .collect();
+
+        import_histories(&items).await?;
+    }
+
+    Ok(rooms)
+}
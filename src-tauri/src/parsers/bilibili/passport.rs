@@ -0,0 +1,120 @@
+use reqwest::header::{HeaderMap, SET_COOKIE};
+use serde::Deserialize;
+
+use crate::error::LsarResult;
+
+pub(super) const PASSPORT_BASE_URL: &str = "https://passport.bilibili.com";
+
+/// 登录成功后需要保存的 cookie
+const LOGIN_COOKIE_NAMES: [&str; 5] = [
+    "SESSDATA",
+    "bili_jct",
+    "DedeUserID",
+    "DedeUserID__ckMd5",
+    "sid",
+];
+
+#[derive(Debug, Deserialize)]
+pub(super) struct Response<T> {
+    pub(super) code: i32,
+    pub(super) message: String,
+    pub(super) data: Option<T>,
+}
+
+/// 登录凭证
+pub struct Credential {
+    pub cookie: String,
+    pub refresh_token: String,
+}
+
+pub(super) fn set_cookies(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// 从 Set-Cookie 中拼出请求用的 cookie，响应头中没有时从跨域登录链接的参数中取
+pub(super) fn build_cookie(set_cookies: &[String], login_url: &str) -> LsarResult<String> {
+    let mut pairs: Vec<(String, String)> = set_cookies
+        .iter()
+        .filter_map(|c| c.split(';').next())
+        .filter_map(|c| c.split_once('='))
+        .map(|(k, v)| (k.trim().to_owned(), v.trim().to_owned()))
+        .collect();
+
+    if pairs.is_empty() && !login_url.is_empty() {
+        pairs = url::Url::parse(login_url)?
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+    }
+
+    let cookie = LOGIN_COOKIE_NAMES
+        .iter()
+        .filter_map(|name| {
+            pairs
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(k, v)| format!("{}={}", k, v))
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    if !cookie.contains("SESSDATA=") {
+        error!("SESSDATA not found in login response");
+        return Err("登录响应中缺少 SESSDATA".into());
+    }
+
+    Ok(cookie)
+}
+
+/// 取出 cookie 中指定字段的值
+pub(super) fn cookie_value<'a>(cookie: &'a str, name: &str) -> Option<&'a str> {
+    cookie
+        .split(';')
+        .filter_map(|c| c.trim().split_once('='))
+        .find(|(k, _)| *k == name)
+        .map(|(_, v)| v)
+}
+
+/// SESSDATA 的过期时间（Unix 秒），格式为 `<token>%2C<过期时间>%2C<校验>`
+pub(super) fn sessdata_expires_at(cookie: &str) -> Option<u64> {
+    let sessdata = cookie_value(cookie, "SESSDATA")?;
+    let sessdata = sessdata.replace("%2C", ",").replace("%2c", ",");
+
+    sessdata.split(',').nth(1)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOKIE: &str =
+        "buvid3=abc; SESSDATA=5b1f8a7c%2C1735660800%2C3a1b2*c1; bili_jct=csrf123; DedeUserID=1";
+
+    #[test]
+    fn test_build_cookie_from_login_url() {
+        let url = "https://passport.biligame.com/x/passport-login/web/crossDomain?DedeUserID=1&DedeUserID__ckMd5=md5&Expires=1&SESSDATA=sess&bili_jct=csrf&gourl=https%3A%2F%2Fwww.bilibili.com";
+        assert_eq!(
+            build_cookie(&[], url).unwrap(),
+            "SESSDATA=sess; bili_jct=csrf; DedeUserID=1; DedeUserID__ckMd5=md5"
+        );
+        assert!(build_cookie(&[], "").is_err());
+    }
+
+    #[test]
+    fn test_cookie_value() {
+        assert_eq!(cookie_value(COOKIE, "bili_jct"), Some("csrf123"));
+        assert_eq!(cookie_value(COOKIE, "sid"), None);
+    }
+
+    #[test]
+    fn test_sessdata_expires_at() {
+        assert_eq!(sessdata_expires_at(COOKIE), Some(1735660800));
+        assert_eq!(sessdata_expires_at("SESSDATA=invalid"), None);
+        assert_eq!(sessdata_expires_at("bili_jct=csrf123"), None);
+    }
+}
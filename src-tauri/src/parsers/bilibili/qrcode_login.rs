@@ -0,0 +1,259 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LsarError, LsarResult};
+
+use super::passport::{build_cookie, set_cookies, Credential, Response, PASSPORT_BASE_URL};
+
+const GENERATE_PATH: &str = "/x/passport-login/web/qrcode/generate";
+const POLL_PATH: &str = "/x/passport-login/web/qrcode/poll";
+
+/// 二维码内容，由前端渲染为二维码
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QrcodeInfo {
+    pub url: String,
+    pub qrcode_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PollData {
+    code: i32,
+    message: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    refresh_token: String,
+}
+
+/// 轮询二维码的结果
+pub enum PollResult {
+    /// 未扫码
+    Waiting,
+    /// 已扫码，等待手机端确认
+    Scanned,
+    /// 二维码已失效
+    Expired,
+    Success(Credential),
+}
+
+pub struct QrcodeLogin<'a> {
+    client: &'a Client,
+    base_url: &'a str,
+}
+
+impl<'a> QrcodeLogin<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self::with_base_url(client, PASSPORT_BASE_URL)
+    }
+
+    pub fn with_base_url(client: &'a Client, base_url: &'a str) -> Self {
+        QrcodeLogin { client, base_url }
+    }
+
+    pub async fn generate(&self) -> LsarResult<QrcodeInfo> {
+        debug!("Generating Bilibili login QR code");
+        let response: Response<QrcodeInfo> = self
+            .client
+            .get(format!("{}{}", self.base_url, GENERATE_PATH))
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Failed to request QR code: {}", e);
+                LsarError::from(e.to_string())
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                error!("Failed to deserialize QR code response: {}", e);
+                LsarError::from(e.to_string())
+            })?;
+
+        if response.code != 0 {
+            error!("Failed to generate QR code: {}", response.message);
+            return Err(LsarError::Other(response.message));
+        }
+
+        response
+            .data
+            .ok_or_else(|| LsarError::from("二维码数据为空"))
+    }
+
+    pub async fn poll(&self, qrcode_key: &str) -> LsarResult<PollResult> {
+        trace!("Polling Bilibili login QR code");
+        let mut url = url::Url::parse(&format!("{}{}", self.base_url, POLL_PATH))?;
+        url.query_pairs_mut().append_pair("qrcode_key", qrcode_key);
+
+        let resp = self.client.get(url).send().await.map_err(|e| {
+            error!("Failed to poll QR code: {}", e);
+            LsarError::from(e.to_string())
+        })?;
+
+        // 登录成功时 cookie 在响应头中，读取响应体之前先取出
+        let set_cookies = set_cookies(resp.headers());
+
+        let response: Response<PollData> = resp.json().await.map_err(|e| {
+            error!("Failed to deserialize poll response: {}", e);
+            LsarError::from(e.to_string())
+        })?;
+
+        if response.code != 0 {
+            error!("Failed to poll QR code: {}", response.message);
+            return Err(LsarError::Other(response.message));
+        }
+
+        let data = response
+            .data
+            .ok_or_else(|| LsarError::from("扫码状态数据为空"))?;
+        debug!("QR code status: {} {}", data.code, data.message);
+
+        match data.code {
+            0 => {
+                let cookie = build_cookie(&set_cookies, &data.url)?;
+                info!("Bilibili QR code login succeeded");
+                Ok(PollResult::Success(Credential {
+                    cookie,
+                    refresh_token: data.refresh_token,
+                }))
+            }
+            86101 => Ok(PollResult::Waiting),
+            86090 => Ok(PollResult::Scanned),
+            86038 => Ok(PollResult::Expired),
+            _ => Err(LsarError::Other(data.message)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    async fn mock_poll(server: &MockServer, key: &str, response: ResponseTemplate) {
+        Mock::given(method("GET"))
+            .and(path(POLL_PATH))
+            .and(query_param("qrcode_key", key))
+            .respond_with(response)
+            .mount(server)
+            .await;
+    }
+
+    fn poll_body(code: i32, message: &str) -> serde_json::Value {
+        json!({
+            "code": 0,
+            "message": "0",
+            "data": {"url": "", "refresh_token": "", "timestamp": 0, "code": code, "message": message}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_generate() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(GENERATE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "code": 0,
+                "message": "0",
+                "data": {
+                    "url": "https://account.bilibili.com/h5/account-h5/auth/scan-web?qrcode_key=abc",
+                    "qrcode_key": "abc"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let info = QrcodeLogin::with_base_url(&client, &server.uri())
+            .generate()
+            .await
+            .unwrap();
+
+        assert_eq!(info.qrcode_key, "abc");
+        assert!(info.url.ends_with("qrcode_key=abc"));
+    }
+
+    #[tokio::test]
+    async fn test_poll_pending_states() {
+        let server = MockServer::start().await;
+        mock_poll(
+            &server,
+            "waiting",
+            ResponseTemplate::new(200).set_body_json(poll_body(86101, "未扫码")),
+        )
+        .await;
+        mock_poll(
+            &server,
+            "scanned",
+            ResponseTemplate::new(200).set_body_json(poll_body(86090, "二维码已扫码未确认")),
+        )
+        .await;
+        mock_poll(
+            &server,
+            "expired",
+            ResponseTemplate::new(200).set_body_json(poll_body(86038, "二维码已失效")),
+        )
+        .await;
+
+        let client = Client::new();
+        let uri = server.uri();
+        let login = QrcodeLogin::with_base_url(&client, &uri);
+
+        assert!(matches!(
+            login.poll("waiting").await.unwrap(),
+            PollResult::Waiting
+        ));
+        assert!(matches!(
+            login.poll("scanned").await.unwrap(),
+            PollResult::Scanned
+        ));
+        assert!(matches!(
+            login.poll("expired").await.unwrap(),
+            PollResult::Expired
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_poll_success() {
+        let server = MockServer::start().await;
+        let body = json!({
+            "code": 0,
+            "message": "0",
+            "data": {
+                "url": "https://passport.biligame.com/x/passport-login/web/crossDomain?DedeUserID=1",
+                "refresh_token": "token123",
+                "timestamp": 1700000000000u64,
+                "code": 0,
+                "message": ""
+            }
+        });
+        mock_poll(
+            &server,
+            "ok",
+            ResponseTemplate::new(200)
+                .append_header("Set-Cookie", "SESSDATA=sess%2C123; Path=/; HttpOnly")
+                .append_header("Set-Cookie", "bili_jct=csrf; Path=/")
+                .append_header("Set-Cookie", "DedeUserID=1; Path=/")
+                .append_header("Set-Cookie", "DedeUserID__ckMd5=md5; Path=/")
+                .append_header("Set-Cookie", "sid=sid1; Path=/")
+                .set_body_json(body),
+        )
+        .await;
+
+        let client = Client::new();
+        let result = QrcodeLogin::with_base_url(&client, &server.uri())
+            .poll("ok")
+            .await
+            .unwrap();
+
+        let PollResult::Success(credential) = result else {
+            panic!("login should succeed");
+        };
+        assert_eq!(
+            credential.cookie,
+            "SESSDATA=sess%2C123; bili_jct=csrf; DedeUserID=1; DedeUserID__ckMd5=md5; sid=sid1"
+        );
+        assert_eq!(credential.refresh_token, "token123");
+    }
+}
@@ -3,6 +3,10 @@ use serde::Deserialize;
 
 use crate::error::{LsarResult, RoomStateError};
 
+use super::wbi::WbiSigner;
+
+const BASE_URL: &str = "https://api.live.bilibili.com/xlive/web-room/v1/index/getInfoByRoom";
+
 #[derive(Debug, Deserialize)]
 struct RoomInfoData {
     anchor_info: AnchorInfo,
@@ -48,10 +52,13 @@ impl<'a> RoomInfoFetcher<'a> {
 
     pub async fn fetch(&self) -> LsarResult<(String, String, String)> {
         debug!("Fetching room info for room ID: {}", self.room_id);
-        let url = format!(
-            "https://api.live.bilibili.com/xlive/web-room/v1/index/getInfoByRoom?room_id={}",
-            self.room_id
-        );
+        let query = WbiSigner::sign_or_unsigned(
+            self.client,
+            self.cookie,
+            &[("room_id", self.room_id.to_string())],
+        )
+        .await?;
+        let url = format!("{}?{}", BASE_URL, query);
         let response = self
             .client
             .get(&url)
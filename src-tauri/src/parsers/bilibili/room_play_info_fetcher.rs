@@ -4,8 +4,102 @@ use serde_json::Value;
 
 use crate::error::{LsarResult, RoomStateError};
 
-// qn 30000 是 B 站支持的最高质量，即播放器中的“杜比”选项，其他质量是 20000 的 4K，15000 的 2K，10000 的 1080P 高帧率，400 的 1080P 低帧率，再往下的质量就没必要看了。在请求时直接使用 30000 发起请求，B 站会返回可用的最高质量。
-const BASE_URL: &str = "https://api.live.bilibili.com/xlive/web-room/v2/index/getRoomPlayInfo?protocol=0,1&format=0,1,2&codec=0,1&qn=30000&platform=web&ptype=8&dolby=5&panorama=1&room_id=";
+use super::wbi::WbiSigner;
+
+const BASE_URL: &str = "https://api.live.bilibili.com/xlive/web-room/v2/index/getRoomPlayInfo";
+
+/// `all_special_types` 中表示付费直播的类型
+const SPECIAL_TYPE_PAID: i64 = 1;
+
+// qn 30000 是 B 站支持的最高质量，即播放器中的“杜比”选项，其他质量是 20000 的 4K，15000 的 2K，10000 的 1080P 高帧率，400 的 1080P 低帧率，再往下的质量就没必要看了。未指定 qn 时直接使用 30000 发起请求，B 站会返回可用的最高质量。
+const HIGHEST_QN: u32 = 30000;
+
+/// 传输协议
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
+    /// http_stream，FLV
+    HttpStream,
+    /// http_hls，TS 或 fMP4
+    HttpHls,
+}
+
+impl Protocol {
+    fn param(&self) -> &'static str {
+        match self {
+            Protocol::HttpStream => "0",
+            Protocol::HttpHls => "1",
+        }
+    }
+
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Protocol::HttpStream => "http_stream",
+            Protocol::HttpHls => "http_hls",
+        }
+    }
+}
+
+/// 封装格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    Flv,
+    Ts,
+    Fmp4,
+}
+
+impl Format {
+    fn param(&self) -> &'static str {
+        match self {
+            Format::Flv => "0",
+            Format::Ts => "1",
+            Format::Fmp4 => "2",
+        }
+    }
+
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Format::Flv => "flv",
+            Format::Ts => "ts",
+            Format::Fmp4 => "fmp4",
+        }
+    }
+}
+
+/// 视频编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Codec {
+    Avc,
+    Hevc,
+}
+
+impl Codec {
+    fn param(&self) -> &'static str {
+        match self {
+            Codec::Avc => "0",
+            Codec::Hevc => "1",
+        }
+    }
+
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Codec::Avc => "avc",
+            Codec::Hevc => "hevc",
+        }
+    }
+}
+
+/// 请求直播流时的选项，未指定的项请求全部可选值
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct PlayOptions {
+    pub qn: Option<u32>,
+    pub protocol: Option<Protocol>,
+    pub format: Option<Format>,
+    pub codec: Option<Codec>,
+}
 
 #[derive(Debug, Deserialize)]
 pub struct CDNItem {
@@ -15,20 +109,32 @@ pub struct CDNItem {
 
 #[derive(Debug, Deserialize)]
 pub struct CodecItem {
+    pub codec_name: String,
+    pub current_qn: u32,
+    #[serde(default)]
+    pub accept_qn: Vec<u32>,
     pub base_url: String,
     pub url_info: Vec<CDNItem>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct FormatItem {
+    pub format_name: String,
     pub codec: Vec<CodecItem>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct StreamItem {
+    pub protocol_name: String,
     pub format: Vec<FormatItem>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct QnDesc {
+    pub qn: u32,
+    pub desc: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub(super) struct PlayUrlInfo {
     pub playurl: PlayUrl,
@@ -36,19 +142,18 @@ pub(super) struct PlayUrlInfo {
 
 #[derive(Debug, Deserialize)]
 pub struct PlayUrl {
+    #[serde(default)]
+    pub g_qn_desc: Vec<QnDesc>,
     pub stream: Vec<StreamItem>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(super) struct ResponseData {
-    live_status: i32, // 0: offline, 1: live, 2: replay
     pub playurl_info: PlayUrlInfo,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Response {
-    code: i32,
-    message: String,
     pub data: ResponseData,
 }
 
@@ -56,23 +161,64 @@ pub struct RoomPlayInfoFetcher<'a> {
     client: &'a Client,
     room_id: u64,
     cookie: &'a str,
+    options: PlayOptions,
+    /// 加密房间的密码
+    password: Option<&'a str>,
 }
 
 impl<'a> RoomPlayInfoFetcher<'a> {
-    pub fn new(client: &'a Client, room_id: u64, cookie: &'a str) -> Self {
+    pub fn new(
+        client: &'a Client,
+        room_id: u64,
+        cookie: &'a str,
+        options: PlayOptions,
+        password: Option<&'a str>,
+    ) -> Self {
         RoomPlayInfoFetcher {
             client,
             room_id,
             cookie,
+            options,
+            password,
         }
     }
 
+    async fn build_url(&self) -> LsarResult<String> {
+        let options = &self.options;
+        let mut params = vec![
+            (
+                "protocol",
+                options.protocol.map_or("0,1", |p| p.param()).to_string(),
+            ),
+            (
+                "format",
+                options.format.map_or("0,1,2", |f| f.param()).to_string(),
+            ),
+            (
+                "codec",
+                options.codec.map_or("0,1", |c| c.param()).to_string(),
+            ),
+            ("qn", options.qn.unwrap_or(HIGHEST_QN).to_string()),
+            ("platform", "web".to_string()),
+            ("ptype", "8".to_string()),
+            ("dolby", "5".to_string()),
+            ("panorama", "1".to_string()),
+            ("room_id", self.room_id.to_string()),
+        ];
+        if let Some(password) = self.password {
+            params.push(("pwd", password.to_string()));
+        }
+
+        let query = WbiSigner::sign_or_unsigned(self.client, self.cookie, &params).await?;
+        Ok(format!("{}?{}", BASE_URL, query))
+    }
+
     pub async fn fetch(&self) -> LsarResult<Response> {
         debug!("Fetching room play info for room ID: {}", self.room_id);
-        let url = format!("{}{}", BASE_URL, self.room_id);
+        let url = self.build_url().await?;
         let response_value = self
             .client
-            .get(&url)
+            .get(url)
             .header("Cookie", self.cookie)
             .send()
             .await
@@ -91,29 +237,144 @@ impl<'a> RoomPlayInfoFetcher<'a> {
 
         debug!("Room play info response: {}", response_value);
 
-        let live_status = response_value["data"]["live_status"].as_i64().unwrap_or(0);
-        if live_status == 0 {
-            return Err(RoomStateError::Offline.into());
+        check_response(&response_value, self.password.is_some())?;
+
+        let response: Response = serde_json::from_value(response_value)?;
+
+        debug!("Successfully fetched room play info");
+        Ok(response)
+    }
+}
+
+/// 先检查接口状态码和开播状态，仅对正在直播的房间检查锁定、加密和付费状态，
+/// 否则未开播的付费或加密房间会被误报为需要付费或密码
+fn check_response(resp: &Value, has_password: bool) -> LsarResult<()> {
+    if resp["code"].as_i64() != Some(0) {
+        let err_msg = format!(
+            "Room play info request unsuccessful: {}",
+            resp["message"].as_str().unwrap_or_default()
+        );
+        warn!("{}", err_msg);
+        return Err(err_msg.into());
+    }
+
+    let data = &resp["data"];
+    match data["live_status"].as_i64().unwrap_or(0) {
+        0 => {
+            info!("Stream is not live");
+            Err(RoomStateError::Offline.into())
         }
-        if live_status == 2 {
-            return Err(RoomStateError::IsReplay.into());
+        2 => Err(RoomStateError::IsReplay.into()),
+        _ => check_room_state(data, has_password),
+    }
+}
+
+/// 检查锁定、加密和付费房间，这些房间不返回直播流
+fn check_room_state(data: &Value, has_password: bool) -> LsarResult<()> {
+    if data["is_locked"].as_bool().unwrap_or(false) {
+        info!("Room is locked until {}", data["lock_till"]);
+        return Err(RoomStateError::Locked.into());
+    }
+
+    if data["encrypted"].as_bool().unwrap_or(false)
+        && !data["pwd_verified"].as_bool().unwrap_or(false)
+    {
+        info!("Room is encrypted, password provided: {}", has_password);
+        return Err(if has_password {
+            RoomStateError::WrongPassword
+        } else {
+            RoomStateError::PasswordRequired
         }
+        .into());
+    }
 
-        let response: Response = serde_json::from_value(response_value)?;
+    // 有观看权限时付费直播间也会返回直播流
+    let is_paid = data["all_special_types"]
+        .as_array()
+        .is_some_and(|types| types.iter().any(|t| t.as_i64() == Some(SPECIAL_TYPE_PAID)));
+    if is_paid && data["playurl_info"].is_null() {
+        info!("Room is paid and current account has no access");
+        return Err(RoomStateError::Paid.into());
+    }
+
+    Ok(())
+}
 
-        if response.code != 0 {
-            let err_msg = format!("Room play info request unsuccessful: {}", response.message);
-            warn!("{}", err_msg);
-            return Err(err_msg.into());
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn state(data: Value, has_password: bool) -> Option<RoomStateError> {
+        match check_room_state(&data, has_password) {
+            Ok(()) => None,
+            Err(crate::error::LsarError::RoomState(e)) => Some(e),
+            Err(e) => panic!("unexpected error: {}", e),
         }
+    }
+
+    #[test]
+    fn test_check_room_state() {
+        assert!(state(json!({"live_status": 1, "playurl_info": {}}), false).is_none());
+        assert!(matches!(
+            state(json!({"is_locked": true, "lock_till": 1700000000}), false),
+            Some(RoomStateError::Locked)
+        ));
+        assert!(matches!(
+            state(json!({"encrypted": true, "pwd_verified": false}), false),
+            Some(RoomStateError::PasswordRequired)
+        ));
+        assert!(matches!(
+            state(json!({"encrypted": true, "pwd_verified": false}), true),
+            Some(RoomStateError::WrongPassword)
+        ));
+        assert!(state(
+            json!({"encrypted": true, "pwd_verified": true, "playurl_info": {}}),
+            true
+        )
+        .is_none());
+        assert!(matches!(
+            state(
+                json!({"all_special_types": [1], "playurl_info": null}),
+                false
+            ),
+            Some(RoomStateError::Paid)
+        ));
+        assert!(state(json!({"all_special_types": [1], "playurl_info": {}}), false).is_none());
+    }
+
+    #[test]
+    fn test_check_response() {
+        let check = |resp: Value| match check_response(&resp, false) {
+            Ok(()) => None,
+            Err(crate::error::LsarError::RoomState(e)) => Some(e),
+            Err(e) => panic!("unexpected error: {}", e),
+        };
 
-        if response.data.live_status == 0 {
-            let err_msg = "Stream is not live";
-            info!("{}", err_msg);
-            return Err(err_msg.into());
+        let paid = json!({"all_special_types": [1], "playurl_info": null});
+        let encrypted = json!({"encrypted": true, "pwd_verified": false});
+        for data in [&paid, &encrypted] {
+            let mut data = data.clone();
+            data["live_status"] = json!(0);
+            assert!(matches!(
+                check(json!({"code": 0, "data": data})),
+                Some(RoomStateError::Offline)
+            ));
         }
 
-        debug!("Successfully fetched room play info");
-        Ok(response)
+        let mut data = paid.clone();
+        data["live_status"] = json!(1);
+        assert!(matches!(
+            check(json!({"code": 0, "data": data})),
+            Some(RoomStateError::Paid)
+        ));
+        assert!(matches!(
+            check(json!({"code": 0, "data": {"live_status": 2}})),
+            Some(RoomStateError::IsReplay)
+        ));
+        assert!(
+            check_response(&json!({"code": 19002005, "message": "房间已加密"}), false).is_err()
+        );
     }
 }
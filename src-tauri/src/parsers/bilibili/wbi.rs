@@ -0,0 +1,259 @@
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use md5::{Digest, Md5};
+use reqwest::Client;
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult};
+use crate::utils::now;
+
+pub(super) const NAV_URL: &str = "https://api.bilibili.com/x/web-interface/nav";
+
+/// 密钥每天更换，缓存一小时足够
+const KEY_TTL: Duration = Duration::from_secs(60 * 60);
+
+const MIXIN_KEY_ENC_TAB: [usize; 64] = [
+    46, 47, 18, 2, 53, 8, 23, 32, 15, 50, 10, 31, 58, 3, 45, 35, 27, 43, 5, 49, 33, 9, 42, 19, 29,
+    28, 14, 39, 12, 38, 41, 13, 37, 48, 7, 16, 24, 55, 40, 61, 26, 17, 0, 1, 60, 51, 30, 4, 22, 25,
+    54, 21, 56, 59, 6, 63, 57, 62, 11, 36, 20, 34, 44, 52,
+];
+
+struct CachedKey {
+    mixin_key: String,
+    fetched_at: Instant,
+}
+
+static MIXIN_KEY: LazyLock<Mutex<Option<CachedKey>>> = LazyLock::new(|| Mutex::new(None));
+
+/// WBI 签名，为请求参数添加 `wts` 和 `w_rid`
+pub struct WbiSigner {
+    mixin_key: String,
+}
+
+impl WbiSigner {
+    pub fn new(img_key: &str, sub_key: &str) -> Self {
+        WbiSigner {
+            mixin_key: mixin_key(img_key, sub_key),
+        }
+    }
+
+    /// 优先使用缓存的密钥，过期时从 nav 接口重新获取，获取失败时沿用过期的密钥
+    pub async fn fetch(client: &Client, cookie: &str) -> LsarResult<Self> {
+        if let Some(mixin_key) = cached_mixin_key(KEY_TTL) {
+            return Ok(WbiSigner { mixin_key });
+        }
+
+        Self::fetch_from_nav(client, cookie).await.or_else(|e| {
+            let mixin_key = cached_mixin_key(Duration::MAX).ok_or(e)?;
+            warn!("Failed to fetch WBI keys, using expired keys");
+            Ok(WbiSigner { mixin_key })
+        })
+    }
+
+    /// 签名请求参数并返回查询字符串，无法获取密钥时返回未签名的查询字符串，
+    /// 部分接口在未签名时仍可使用
+    pub async fn sign_or_unsigned(
+        client: &Client,
+        cookie: &str,
+        params: &[(&str, String)],
+    ) -> LsarResult<String> {
+        match Self::fetch(client, cookie).await {
+            Ok(signer) => signer.sign(params),
+            Err(e) => {
+                warn!("Failed to fetch WBI keys, sending request unsigned: {}", e);
+                Ok(unsigned_query(params))
+            }
+        }
+    }
+
+    async fn fetch_from_nav(client: &Client, cookie: &str) -> LsarResult<Self> {
+        debug!("Fetching WBI keys");
+        let nav = client
+            .get(NAV_URL)
+            .header("Cookie", cookie)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Failed to request nav: {}", e);
+                LsarError::from(e.to_string())
+            })?
+            .json::<Value>()
+            .await
+            .map_err(|e| {
+                error!("Failed to deserialize nav response: {}", e);
+                LsarError::from(e.to_string())
+            })?;
+
+        let signer = Self::from_nav(&nav).ok_or_else(|| {
+            error!("WBI keys not found in nav response");
+            LsarError::from("获取 WBI 密钥失败")
+        })?;
+
+        Ok(signer)
+    }
+
+    /// 从 nav 接口的响应中取出密钥并缓存，未登录时响应中同样有密钥
+    pub fn from_nav(nav: &Value) -> Option<Self> {
+        let wbi_img = &nav["data"]["wbi_img"];
+        let img_key = key_from_url(wbi_img["img_url"].as_str()?)?;
+        let sub_key = key_from_url(wbi_img["sub_url"].as_str()?)?;
+
+        let signer = Self::new(img_key, sub_key);
+        if let Ok(mut cached) = MIXIN_KEY.lock() {
+            *cached = Some(CachedKey {
+                mixin_key: signer.mixin_key.clone(),
+                fetched_at: Instant::now(),
+            });
+        }
+
+        Some(signer)
+    }
+
+    /// 使用当前时间签名，返回可直接拼接在链接后的查询字符串
+    pub fn sign(&self, params: &[(&str, String)]) -> LsarResult<String> {
+        Ok(self.sign_with_timestamp(params, now()?.as_secs()))
+    }
+
+    fn sign_with_timestamp(&self, params: &[(&str, String)], wts: u64) -> String {
+        let wts = wts.to_string();
+        let mut params: Vec<(&str, &str)> = params
+            .iter()
+            .map(|(k, v)| (*k, v.as_str()))
+            .chain([("wts", wts.as_str())])
+            .collect();
+        params.sort_by_key(|(k, _)| *k);
+
+        let query = params
+            .iter()
+            .map(|(k, v)| {
+                // 值中的 !'()* 需要去掉
+                let v: String = v.chars().filter(|c| !"!'()*".contains(*c)).collect();
+                format!("{}={}", encode_uri_component(k), encode_uri_component(&v))
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut hasher = Md5::new();
+        hasher.update(format!("{}{}", query, self.mixin_key));
+        let w_rid = format!("{:x}", hasher.finalize());
+
+        format!("{}&w_rid={}", query, w_rid)
+    }
+}
+
+fn cached_mixin_key(ttl: Duration) -> Option<String> {
+    let cached = MIXIN_KEY.lock().ok()?;
+    cached
+        .as_ref()
+        .filter(|k| k.fetched_at.elapsed() < ttl)
+        .map(|k| k.mixin_key.clone())
+}
+
+fn unsigned_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(k, v)| format!("{}={}", encode_uri_component(k), encode_uri_component(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn mixin_key(img_key: &str, sub_key: &str) -> String {
+    let raw: Vec<u8> = format!("{}{}", img_key, sub_key).into_bytes();
+
+    MIXIN_KEY_ENC_TAB
+        .iter()
+        .filter_map(|i| raw.get(*i).map(|b| *b as char))
+        .take(32)
+        .collect()
+}
+
+/// `https://i0.hdslb.com/bfs/wbi/<key>.png` → `<key>`
+fn key_from_url(url: &str) -> Option<&str> {
+    let file = url.rsplit('/').next()?;
+    file.split('.').next().filter(|key| !key.is_empty())
+}
+
+/// 与 JavaScript 的 `encodeURIComponent` 一致，十六进制使用大写
+fn encode_uri_component(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const IMG_KEY: &str = "7cd084941338484aae1ad9425b84077c";
+    const SUB_KEY: &str = "4932caff0ff746eab6f01bf08b70ac45";
+
+    #[test]
+    fn test_mixin_key() {
+        assert_eq!(
+            mixin_key(IMG_KEY, SUB_KEY),
+            "ea1db124af3c7062474693fa704f4ff8"
+        );
+    }
+
+    #[test]
+    fn test_sign() {
+        let signer = WbiSigner::new(IMG_KEY, SUB_KEY);
+        let params = [
+            ("foo", "114".to_string()),
+            ("bar", "514".to_string()),
+            ("zab", "1919810".to_string()),
+        ];
+
+        assert_eq!(
+            signer.sign_with_timestamp(&params, 1702204169),
+            "bar=514&foo=114&wts=1702204169&zab=1919810&w_rid=8f6f2b5b3d485fe1886cec6a0be8c5d4"
+        );
+    }
+
+    #[test]
+    fn test_sign_filters_and_encodes_values() {
+        let signer = WbiSigner::new(IMG_KEY, SUB_KEY);
+        let params = [("keyword", "a b(c)!*'中".to_string())];
+
+        assert_eq!(
+            signer.sign_with_timestamp(&params, 1702204169),
+            "keyword=a%20bc%E4%B8%AD&wts=1702204169&w_rid=38c78704a0d0ec4edbb2d557eac42993"
+        );
+    }
+
+    #[test]
+    fn test_unsigned_query() {
+        let params = [
+            ("room_id", "21452505".to_string()),
+            ("q", "a b".to_string()),
+        ];
+        assert_eq!(unsigned_query(&params), "room_id=21452505&q=a%20b");
+    }
+
+    #[test]
+    fn test_from_nav() {
+        let nav = json!({
+            "code": -101,
+            "message": "账号未登录",
+            "data": {
+                "isLogin": false,
+                "wbi_img": {
+                    "img_url": format!("https://i0.hdslb.com/bfs/wbi/{}.png", IMG_KEY),
+                    "sub_url": format!("https://i0.hdslb.com/bfs/wbi/{}.png", SUB_KEY)
+                }
+            }
+        });
+
+        let signer = WbiSigner::from_nav(&nav).unwrap();
+        assert_eq!(signer.mixin_key, "ea1db124af3c7062474693fa704f4ff8");
+        assert!(WbiSigner::from_nav(&json!({"data": {}})).is_none());
+    }
+}
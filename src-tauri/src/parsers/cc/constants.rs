@@ -0,0 +1,28 @@
+/// 主播直播信息，通过 ccid 查询频道号
+pub(super) const ANCHOR_LIVES_URL: &str = "https://api.cc.163.com/v1/activitylives/anchor/lives";
+/// 频道信息和直播流
+pub(super) const CHANNEL_URL: &str = "https://cc.163.com/live/channel/";
+
+/// 画质从高到低，`quickplay.resolution` 中的键和名称
+pub(super) const QUALITIES: [(&str, &str); 6] = [
+    ("original", "原画"),
+    ("blueray", "蓝光"),
+    ("ultra", "超清"),
+    ("high", "高清"),
+    ("standard", "标清"),
+    ("medium", "流畅"),
+];
+
+/// CDN 的名称，未收录的直接显示原名
+pub(super) fn cdn_name(cdn: &str) -> &str {
+    match cdn {
+        "ali" => "阿里云",
+        "hs" => "火山",
+        "ks" => "金山云",
+        "ws" => "网宿",
+        "dn" => "帝联",
+        "xy" => "星域",
+        "wy" => "网易",
+        _ => cdn,
+    }
+}
@@ -0,0 +1,189 @@
+mod constants;
+
+use serde_json::Value;
+
+use crate::error::{LsarResult, MissKeyFieldError, RoomStateError};
+use crate::network::http::Client;
+use crate::parsers::{Link, ParsedResult, Parser, Quality};
+use crate::platform::Platform;
+
+use self::constants::{cdn_name, ANCHOR_LIVES_URL, CHANNEL_URL, QUALITIES};
+
+pub struct CCParser {
+    /// 直播间链接中的 ccid
+    room_id: u64,
+    http_client: Client,
+}
+
+impl CCParser {
+    fn new(room_id: u64) -> Self {
+        Self {
+            room_id,
+            http_client: Client::new(),
+        }
+    }
+
+    /// 通过 ccid 获取频道号，未开播的主播不在返回结果中
+    async fn get_channel_id(&self) -> LsarResult<u64> {
+        let url = format!("{}?anchor_ccid={}", ANCHOR_LIVES_URL, self.room_id);
+        let resp: Value = self.http_client.get_json(&url, None).await?;
+        debug!("CC anchor lives response: {}", resp);
+
+        channel_id_from_lives(&resp, self.room_id)
+    }
+
+    async fn get_channel(&self, channel_id: u64) -> LsarResult<Value> {
+        let url = format!("{}?channelids={}", CHANNEL_URL, channel_id);
+        let resp: Value = self.http_client.get_json(&url, None).await?;
+        trace!("CC channel response: {}", resp);
+
+        let channel = resp["data"][0].clone();
+        if channel.is_null() {
+            error!("CC channel {} not found", channel_id);
+            return Err(RoomStateError::NotExists.into());
+        }
+
+        Ok(channel)
+    }
+}
+
+fn channel_id_from_lives(resp: &Value, ccid: u64) -> LsarResult<u64> {
+    if resp["code"].as_str().is_some_and(|code| code != "OK") {
+        error!("CC anchor lives request failed: {}", resp);
+        return Err(RoomStateError::NotExists.into());
+    }
+
+    let live = &resp["data"][ccid.to_string()];
+    if live.is_null() {
+        info!("CC anchor {} is offline", ccid);
+        return Err(RoomStateError::Offline.into());
+    }
+
+    live["channel_id"]
+        .as_u64()
+        .ok_or_else(|| MissKeyFieldError::RoomId.into())
+}
+
+/// 每个画质、每条线路一个链接
+fn parse_streams(channel: &Value) -> LsarResult<(Vec<Link>, Vec<Quality>)> {
+    // 轮播时主播不在线，播放的是录像
+    if channel["is_loop"].as_i64().unwrap_or(0) == 1 {
+        info!("CC channel is looping recorded videos");
+        return Err(RoomStateError::IsReplay.into());
+    }
+
+    let resolution = channel["quickplay"]["resolution"]
+        .as_object()
+        .ok_or(RoomStateError::Offline)?;
+
+    let mut links = Vec::new();
+    let mut qualities = Vec::new();
+    for (index, (key, name)) in QUALITIES.iter().enumerate() {
+        let Some(cdns) = resolution.get(*key).and_then(|r| r["cdn"].as_object()) else {
+            continue;
+        };
+
+        qualities.push(Quality::new((QUALITIES.len() - index) as i64, *name));
+        links.extend(cdns.iter().filter_map(|(cdn, url)| {
+            Some(
+                Link::new(url.as_str()?)
+                    .quality(*name)
+                    .cdn(cdn_name(cdn))
+                    .format("FLV"),
+            )
+        }));
+    }
+
+    if links.is_empty() {
+        info!("CC channel has no stream");
+        return Err(RoomStateError::Offline.into());
+    }
+
+    Ok((links, qualities))
+}
+
+impl Parser for CCParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let channel_id = self.get_channel_id().await?;
+        let channel = self.get_channel(channel_id).await?;
+        let (links, qualities) = parse_streams(&channel)?;
+
+        let string = |key: &str| channel[key].as_str().unwrap_or_default().to_string();
+        Ok(ParsedResult {
+            platform: Platform::CC,
+            title: string("title"),
+            anchor: string("nickname"),
+            room_id: self.room_id.to_string(),
+            category: string("gamename"),
+            links,
+            qualities,
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+}
+
+#[tauri::command]
+pub async fn parse_cc(room_id: u64) -> LsarResult<ParsedResult> {
+    info!("Parsing CC room: {}", room_id);
+    let mut cc = CCParser::new(room_id);
+    cc.parse().await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::error::LsarError;
+
+    use super::*;
+
+    #[test]
+    fn test_channel_id_from_lives() {
+        let resp =
+            json!({"code": "OK", "data": {"361433": {"channel_id": 4787, "room_id": 361433}}});
+        assert_eq!(channel_id_from_lives(&resp, 361433).unwrap(), 4787);
+
+        assert!(matches!(
+            channel_id_from_lives(&json!({"code": "OK", "data": {}}), 361433),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+        assert!(matches!(
+            channel_id_from_lives(&json!({"code": "ROOM_NOT_EXIST"}), 1),
+            Err(LsarError::RoomState(RoomStateError::NotExists))
+        ));
+    }
+
+    #[test]
+    fn test_parse_streams() {
+        let channel = json!({
+            "quickplay": {
+                "resolution": {
+                    "high": {"vbr": 1000, "cdn": {"ws": "https://ws.example.com/high.flv"}},
+                    "blueray": {
+                        "vbr": 4000,
+                        "cdn": {"ali": "https://ali.example.com/bd.flv", "ks": "https://ks.example.com/bd.flv"}
+                    }
+                }
+            }
+        });
+        let (links, qualities) = parse_streams(&channel).unwrap();
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[0].quality.as_deref(), Some("蓝光"));
+        assert_eq!(links[0].cdn.as_deref(), Some("阿里云"));
+        assert_eq!(links[2].cdn.as_deref(), Some("网宿"));
+        let names: Vec<&str> = qualities.iter().map(|q| q.name.as_str()).collect();
+        assert_eq!(names, vec!["蓝光", "高清"]);
+
+        assert!(matches!(
+            parse_streams(&json!({"is_loop": 1})),
+            Err(LsarError::RoomState(RoomStateError::IsReplay))
+        ));
+        assert!(matches!(
+            parse_streams(&json!({})),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+mod playback;
+
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE};
+use serde_json::Value;
+use url::Url;
+
+use crate::config::Config;
+use crate::error::{LsarError, LsarResult, MissKeyFieldError};
+use crate::network::http::Client;
+use crate::parsers::hls::{into_links, parse_master_playlist};
+use crate::parsers::{ParsedResult, Parser};
+use crate::platform::Platform;
+
+use self::playback::{parse_media, parse_playback, parse_qualities};
+
+const API_BASE_URL: &str = "https://api.chzzk.naver.com/service/v3/channels/";
+
+pub struct ChzzkParser {
+    channel_id: String,
+    /// Naver 账号的 cookie，可以为空
+    cookie: String,
+    http_client: Client,
+}
+
+impl ChzzkParser {
+    fn new(channel_id: String, cookie: String) -> Self {
+        Self {
+            channel_id,
+            cookie,
+            http_client: Client::new(),
+        }
+    }
+
+    async fn get_live_detail(&self) -> LsarResult<Value> {
+        let url = format!("{}{}/live-detail", API_BASE_URL, self.channel_id);
+
+        let mut headers = HeaderMap::new();
+        if !self.cookie.is_empty() {
+            headers.insert(
+                COOKIE,
+                HeaderValue::from_str(&self.cookie).map_err(|e| e.to_string())?,
+            );
+        }
+
+        let resp: Value = self.http_client.get_json(&url, headers).await?;
+        trace!("CHZZK live detail response: {}", resp);
+
+        Ok(resp["content"].clone())
+    }
+}
+
+impl Parser for ChzzkParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let content = self.get_live_detail().await?;
+        let playback = parse_playback(&content, !self.cookie.is_empty())?;
+
+        let mut links = Vec::new();
+        for media in parse_media(&playback) {
+            let url = Url::parse(&media.path)?;
+            let playlist = self.http_client.get_text(url.as_str()).await?;
+            let (media_links, _) = into_links(parse_master_playlist(&url, &playlist)?);
+            links.extend(
+                media_links
+                    .into_iter()
+                    .map(|link| link.format(media.format())),
+            );
+        }
+
+        let string = |value: &Value| value.as_str().unwrap_or_default().to_string();
+        Ok(ParsedResult {
+            platform: Platform::Chzzk,
+            title: string(&content["liveTitle"]),
+            anchor: string(&content["channel"]["channelName"]),
+            room_id: self.channel_id.clone(),
+            category: string(&content["liveCategoryValue"]),
+            links,
+            qualities: parse_qualities(&playback),
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+}
+
+/// 从 `chzzk.naver.com/live/<频道 ID>` 或频道主页链接中取出频道 ID
+fn resolve_channel_id(input: &str) -> LsarResult<String> {
+    let url = Url::parse(input)?;
+    if url.host_str() != Some("chzzk.naver.com") {
+        error!("Unsupported CHZZK URL: {}", url);
+        return Err(LsarError::Other(format!("不支持的 CHZZK 链接: {}", url)));
+    }
+
+    // 频道 ID 是 32 位十六进制字符串
+    url.path_segments()
+        .into_iter()
+        .flatten()
+        .find(|s| s.len() == 32 && s.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_string)
+        .ok_or_else(|| MissKeyFieldError::RoomId.into())
+}
+
+#[tauri::command]
+pub async fn parse_chzzk(url: String) -> LsarResult<ParsedResult> {
+    let channel_id = resolve_channel_id(&url)?;
+    info!("Parsing CHZZK channel: {}", channel_id);
+
+    let config = Config::read_from_file()?;
+    let mut chzzk = ChzzkParser::new(channel_id, config.chzzk_cookie().to_string());
+    chzzk.parse().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_channel_id() {
+        assert_eq!(
+            resolve_channel_id("https://chzzk.naver.com/live/c68b8ef525fb3d2fa146344d84991753")
+                .unwrap(),
+            "c68b8ef525fb3d2fa146344d84991753"
+        );
+        assert_eq!(
+            resolve_channel_id("https://chzzk.naver.com/c68b8ef525fb3d2fa146344d84991753").unwrap(),
+            "c68b8ef525fb3d2fa146344d84991753"
+        );
+        assert!(resolve_channel_id("https://chzzk.naver.com/live/abc").is_err());
+        assert!(
+            resolve_channel_id("https://www.naver.com/live/c68b8ef525fb3d2fa146344d84991753")
+                .is_err()
+        );
+    }
+}
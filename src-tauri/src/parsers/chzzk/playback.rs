@@ -0,0 +1,176 @@
+use std::cmp::Reverse;
+
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult, RequestError, RoomStateError};
+use crate::parsers::Quality;
+
+/// `livePlaybackJson` 中的一个媒体，CHZZK 同时提供普通 HLS 和低延迟的 LL-HLS
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Media {
+    /// `HLS` 或 `LLHLS`
+    pub media_id: String,
+    /// 主播放列表地址
+    pub path: String,
+}
+
+impl Media {
+    pub fn format(&self) -> &'static str {
+        if self.media_id == "LLHLS" {
+            "LL-HLS"
+        } else {
+            "HLS"
+        }
+    }
+}
+
+/// 检查直播状态并解码 `livePlaybackJson`
+pub(super) fn parse_playback(content: &Value, has_cookie: bool) -> LsarResult<Value> {
+    if content.is_null() {
+        info!("CHZZK channel does not exist");
+        return Err(RoomStateError::NotExists.into());
+    }
+
+    if content["status"].as_str() != Some("OPEN") {
+        info!("CHZZK channel is offline");
+        return Err(RoomStateError::Offline.into());
+    }
+
+    let Some(playback) = content["livePlaybackJson"].as_str() else {
+        // 年龄限制的直播只对已登录且通过认证的账号返回直播流
+        if content["adult"].as_bool().unwrap_or(false) {
+            warn!(
+                "CHZZK stream is age restricted, user status: {}",
+                content["userAdultStatus"]
+            );
+            return Err(if has_cookie {
+                RoomStateError::Restricted.into()
+            } else {
+                RequestError::LoginRequired.into()
+            });
+        }
+
+        error!("No livePlaybackJson in CHZZK response: {}", content);
+        return Err(LsarError::from("CHZZK 获取直播流失败"));
+    };
+
+    Ok(serde_json::from_str(playback)?)
+}
+
+pub(super) fn parse_media(playback: &Value) -> Vec<Media> {
+    playback["media"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|media| media["protocol"].as_str() == Some("HLS"))
+        .filter_map(|media| {
+            Some(Media {
+                media_id: media["mediaId"].as_str()?.to_string(),
+                path: media["path"].as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 画质来自 `encodingTrack`，值为视频高度，如 `1080p60`
+pub(super) fn parse_qualities(playback: &Value) -> Vec<Quality> {
+    let mut qualities: Vec<Quality> = Vec::new();
+    let tracks = playback["media"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|media| media["encodingTrack"].as_array())
+        .flatten();
+
+    for track in tracks {
+        let Some(height) = track["videoHeight"].as_i64() else {
+            // 纯音频的轨道没有分辨率
+            continue;
+        };
+        if qualities.iter().any(|q| q.value == height) {
+            continue;
+        }
+
+        let frame_rate = track["videoFrameRate"]
+            .as_str()
+            .and_then(|f| f.parse::<f64>().ok())
+            .unwrap_or_default();
+        let name = if frame_rate > 30.5 {
+            format!("{}p{}", height, frame_rate.round())
+        } else {
+            format!("{}p", height)
+        };
+        qualities.push(Quality::new(height, name));
+    }
+    qualities.sort_unstable_by_key(|q| Reverse(q.value));
+
+    qualities
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn playback() -> Value {
+        json!({
+            "media": [
+                {
+                    "mediaId": "HLS",
+                    "protocol": "HLS",
+                    "path": "https://livecloud.pstatic.net/chzzk/lip2_kr/abc/hls_playlist.m3u8",
+                    "encodingTrack": [
+                        {"encodingTrackId": "1080p", "videoHeight": 1080, "videoFrameRate": "60.0"},
+                        {"encodingTrackId": "720p", "videoHeight": 720, "videoFrameRate": "30.0"},
+                        {"encodingTrackId": "alow.stream", "audioOnly": true}
+                    ]
+                },
+                {
+                    "mediaId": "LLHLS",
+                    "protocol": "HLS",
+                    "path": "https://livecloud.pstatic.net/chzzk/lip2_kr/abc/llhls_playlist.m3u8",
+                    "encodingTrack": [{"encodingTrackId": "1080p", "videoHeight": 1080, "videoFrameRate": "60.0"}]
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_parse_playback() {
+        let content = json!({"status": "OPEN", "livePlaybackJson": playback().to_string()});
+        let playback = parse_playback(&content, false).unwrap();
+
+        let media = parse_media(&playback);
+        assert_eq!(media.len(), 2);
+        assert_eq!(media[1].format(), "LL-HLS");
+
+        let names: Vec<String> = parse_qualities(&playback)
+            .into_iter()
+            .map(|q| q.name)
+            .collect();
+        assert_eq!(names, vec!["1080p60", "720p"]);
+    }
+
+    #[test]
+    fn test_parse_playback_states() {
+        assert!(matches!(
+            parse_playback(&Value::Null, false),
+            Err(LsarError::RoomState(RoomStateError::NotExists))
+        ));
+        assert!(matches!(
+            parse_playback(&json!({"status": "CLOSE"}), false),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+
+        let adult = json!({"status": "OPEN", "adult": true, "livePlaybackJson": null});
+        assert!(matches!(
+            parse_playback(&adult, false),
+            Err(LsarError::Request(RequestError::LoginRequired))
+        ));
+        assert!(matches!(
+            parse_playback(&adult, true),
+            Err(LsarError::RoomState(RoomStateError::Restricted))
+        ));
+    }
+}
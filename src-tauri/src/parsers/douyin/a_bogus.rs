@@ -5,6 +5,9 @@ use sm3::{Digest, Sm3};
 
 use crate::utils::now_millis;
 
+/// 与 [`ABogusGenerator`] 中固定的 UA 编码数组对应的 User-Agent，签名请求时必须使用此 UA
+pub(super) const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/90.0.4430.212 Safari/537.36";
+
 /// Base64 编码字符集的不同变体
 #[allow(dead_code)]
 enum Base64Charset {
@@ -248,22 +251,16 @@ impl ABogusGenerator {
         ]
     }
 
-    /// 生成第一部分字符串（由三个随机序列组成）
+    /// 生成第一部分字节（由三个随机序列组成）
     fn generate_first_component(
         seed1: Option<u64>,
         seed2: Option<u64>,
         seed3: Option<u64>,
-    ) -> String {
-        let seq1 = Self::generate_random_sequence_type1(seed1, None, None, None);
-        let seq2 = Self::generate_random_sequence_type2(seed2, None, None);
-        let seq3 = Self::generate_random_sequence_type3(seed3, None, None);
-
-        format!(
-            "{}{}{}",
-            String::from_utf8_lossy(&seq1),
-            String::from_utf8_lossy(&seq2),
-            String::from_utf8_lossy(&seq3)
-        )
+    ) -> Vec<u8> {
+        let mut component = Self::generate_random_sequence_type1(seed1, None, None, None);
+        component.extend(Self::generate_random_sequence_type2(seed2, None, None));
+        component.extend(Self::generate_random_sequence_type3(seed3, None, None));
+        component
     }
 
     /// 计算 SM3 哈希值
@@ -304,8 +301,7 @@ impl ABogusGenerator {
         let actual_end_time = if end_time > 0 {
             end_time
         } else {
-            // TODO: 添加 4-8 毫秒的随机延迟
-            now_millis().unwrap() + 8
+            actual_start_time + rand::rng().random_range(4..=8)
         };
 
         let params_hash = Self::generate_params_hash(params);
@@ -329,7 +325,7 @@ impl ABogusGenerator {
     }
 
     /// RC4 加密算法实现
-    fn rc4_encrypt(plaintext: &[u8], key: &[u8]) -> String {
+    fn rc4_encrypt(plaintext: &[u8], key: &[u8]) -> Vec<u8> {
         // 初始化 S 盒
         let mut s_box: Vec<u8> = (0..=255).collect();
         let mut j = 0usize;
@@ -354,17 +350,17 @@ impl ABogusGenerator {
             ciphertext.push(byte ^ keystream_byte);
         }
 
-        String::from_utf8_lossy(&ciphertext).to_string()
+        ciphertext
     }
 
-    /// 生成第二部分字符串（加密后的数据）
+    /// 生成第二部分字节（加密后的数据）
     fn generate_second_component(
         &self,
         params: &str,
         method: &str,
         start_time: u64,
         end_time: u64,
-    ) -> String {
+    ) -> Vec<u8> {
         let mut data = self.generate_second_component_bytes(params, method, start_time, end_time);
         let checksum = Self::calculate_xor_checksum(&data);
 
@@ -435,16 +431,16 @@ impl ABogusGenerator {
         seed2: Option<u64>,
         seed3: Option<u64>,
     ) -> String {
-        let first_component = Self::generate_first_component(seed1, seed2, seed3);
-        let second_component = self.generate_second_component(
+        // 两部分均为 0-255 的字符编码，必须按字节拼接，不能作为 UTF-8 字符串处理
+        let mut combined_data = Self::generate_first_component(seed1, seed2, seed3);
+        combined_data.extend(self.generate_second_component(
             params,
             "GET",
             start_time.unwrap_or(0),
             end_time.unwrap_or(0),
-        );
+        ));
 
-        let combined_data = format!("{}{}", first_component, second_component);
-        Self::encode_with_custom_base64(combined_data.as_bytes(), &Base64Charset::CustomVariant4)
+        Self::encode_with_custom_base64(&combined_data, &Base64Charset::CustomVariant4)
     }
 }
 
@@ -512,3 +508,72 @@ impl ByteArrayParams {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 以下两个 a_bogus 向量由本实现在固定时间戳和随机数下生成，只用于发现回归，
+    // 不是从 douyin.com 的请求中抓取的。签名算法的正确性由下面 SM3 和 RC4 的标准向量保证，
+    // 抓取到真实请求的参数、时间戳和 a_bogus 后应替换为抓取的向量并注明来源
+    #[test]
+    fn test_generate_a_bogus_default_fingerprint() {
+        let generator = ABogusGenerator::new(None);
+        let a_bogus = generator.generate_a_bogus(
+            "device_platform=webapp&aid=6383&channel=channel_pc_web&web_rid=745964462470",
+            Some(1718000000000),
+            Some(1718000000006),
+            Some(1234),
+            Some(5678),
+            Some(9012),
+        );
+        assert_eq!(
+            a_bogus,
+            "E7mhBdugDifihdWk5RVLfY3q636VYmQy0SVkMD2fcBDO5L39HMY29exowGJvYY8jNs/DIeEjy4hbT3ohrQ2y0Hwf9W0L/25ksDSkKl5Q5xSSs1X9eghgJ04qmkt5SMx2RvB-rOXmqhZHKRbp09oHmhK4b1dzFgf3qJLzyj=="
+        );
+    }
+
+    #[test]
+    fn test_generate_a_bogus_win32_fingerprint() {
+        let generator = ABogusGenerator::new(Some("Win32"));
+        let a_bogus = generator.generate_a_bogus(
+            "aid=6383&app_name=douyin_web&live_id=1&web_rid=745964462470&msToken=abc",
+            Some(1760000000000),
+            Some(1760000000005),
+            Some(42),
+            Some(4242),
+            Some(9999),
+        );
+        assert_eq!(
+            a_bogus,
+            "QjmhQm8fDkDBkVWh56nLfY3q66WVYmsl0SVkMD2fcBDO0L39HMYh9exo1HUveY8ji4/sIeyjy4hbO3xprQAjM36UHWwEUdQ2mgukKl5Q5I0j53iruyRkrzDF-vG1SaBBRk-lrOX0w7lHFb8pAnAn-h2UbfebYrtswnuYt9/bhj=="
+        );
+    }
+
+    #[test]
+    fn test_compute_sm3_hash() {
+        // GB/T 32905-2016 附录 A 的示例
+        let hex =
+            |bytes: Vec<u8>| -> String { bytes.iter().map(|b| format!("{:02x}", b)).collect() };
+        assert_eq!(
+            hex(ABogusGenerator::compute_sm3_hash(b"abc")),
+            "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0"
+        );
+        assert_eq!(
+            hex(ABogusGenerator::compute_sm3_hash(
+                "abcd".repeat(16).as_bytes()
+            )),
+            "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732"
+        );
+    }
+
+    #[test]
+    fn test_rc4_encrypt() {
+        // 维基百科 RC4 词条中的测试向量
+        let ciphertext = ABogusGenerator::rc4_encrypt(b"Plaintext", b"Key");
+        assert_eq!(
+            ciphertext,
+            [0xBB, 0xF3, 0x16, 0xE8, 0xD9, 0x40, 0xAF, 0x0A, 0xD3]
+        );
+    }
+}
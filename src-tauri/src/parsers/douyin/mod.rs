@@ -1,65 +1,163 @@
-// mod a_bogus;
+mod a_bogus;
 mod models;
-// mod ms_token;
-// mod utils;
+mod ms_token;
+mod room_resolver;
+mod utils;
 
 use std::collections::HashMap;
 
 use regex::Regex;
+use reqwest::header::{
+    HeaderMap, HeaderValue, COOKIE, REFERER, UPGRADE_INSECURE_REQUESTS, USER_AGENT,
+};
 use serde_json::Value;
+use url::Url;
 
-use crate::error::{LsarError, LsarResult, RoomStateError};
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
 use crate::network::http::Client;
 use crate::parsers::douyin::models::{RoomData, UserInfo};
-use crate::parsers::ParsedResult;
+use crate::parsers::{Link, ParsedResult};
 use crate::platform::Platform;
 
 use super::Parser;
 
+use self::a_bogus::ABogusGenerator;
 use self::models::{PartitionRoadMap, Resolution, RoomInfo, StreamData};
+use self::ms_token::generate_ms_token;
+use self::room_resolver::RoomResolver;
+use self::utils::{get_ac_nonce, get_ttwid};
 
 const DOUYIN_LIVE_BASE_URL: &str = "https://live.douyin.com";
+const ROOM_ENTER_API: &str = "https://live.douyin.com/webcast/room/web/enter/";
+/// 签名时使用的浏览器平台，需与请求参数中的 `browser_platform` 一致
+const BROWSER_PLATFORM: &str = "Win32";
 // const ROOM_CLOSED_MESSAGE: &str = "该内容暂时无法无法查看";
 
 pub struct DouyinParser {
+    /// 网页端房间号（web_rid），为 0 时从 `url` 中解析
     room_id: u64,
-    room_url: String,
+    url: Option<String>,
     client: Client,
+    /// 签名接口请求头，包含 `__ac_nonce` 和 `ttwid` cookie
+    headers: HeaderMap,
 }
 
 impl DouyinParser {
-    pub fn new(room_id: u64) -> Self {
+    pub fn new(room_id: u64, url: Option<String>) -> Self {
         DouyinParser {
             room_id,
-            room_url: format!("{}/{}", DOUYIN_LIVE_BASE_URL, room_id),
+            url,
             client: Client::new(),
+            headers: HeaderMap::new(),
         }
     }
 
-    // async fn configure_request_headers(&mut self) -> LsarResult<()> {
-    //     debug!("Configuring request headers for room {}", self.room_id);
+    fn room_url(&self) -> String {
+        format!("{}/{}", DOUYIN_LIVE_BASE_URL, self.room_id)
+    }
+
+    /// 将分享短链、用户主页、回流链接等解析为网页端房间号
+    async fn resolve_room_id(&mut self) -> LsarResult<()> {
+        if self.room_id != 0 {
+            return Ok(());
+        }
+
+        let url = self.url.as_deref().ok_or(MissKeyFieldError::RoomId)?;
+        self.room_id = RoomResolver::new(&self.client).resolve(url).await?;
 
-    //     self.client.insert_header(UPGRADE_INSECURE_REQUESTS, "1")?;
+        Ok(())
+    }
+
+    async fn configure_request_headers(&mut self) -> LsarResult<()> {
+        debug!("Configuring request headers for room {}", self.room_id);
 
-    //     let ac_nonce = get_ac_nonce(&self.client, &self.room_url).await?;
+        let room_url = self.room_url();
+        let mut headers = HeaderMap::new();
+        headers.insert(UPGRADE_INSECURE_REQUESTS, HeaderValue::from_static("1"));
+        headers.insert(USER_AGENT, HeaderValue::from_static(a_bogus::USER_AGENT));
+        headers.insert(REFERER, header_value(&room_url)?);
 
-    //     let cookie = format!("__ac_nonce={}", ac_nonce);
-    //     self.client.insert_header(COOKIE, &cookie)?;
+        let ac_nonce = get_ac_nonce(&self.client, &room_url, &headers).await?;
 
-    //     let ttwid = get_ttwid(&self.client, &self.room_url).await?;
+        let cookie = format!("__ac_nonce={}", ac_nonce);
+        headers.insert(COOKIE, header_value(&cookie)?);
 
-    //     let cookie = format!("__ac_nonce={}; ttwid={}", ac_nonce, ttwid);
-    //     self.client.insert_header(COOKIE, &cookie)?;
+        let ttwid = get_ttwid(&self.client, &room_url, &headers).await?;
 
-    //     debug!("Request headers configured successfully");
-    //     Ok(())
-    // }
+        let cookie = format!("__ac_nonce={}; ttwid={}", ac_nonce, ttwid);
+        headers.insert(COOKIE, header_value(&cookie)?);
+
+        self.headers = headers;
+
+        debug!("Request headers configured successfully");
+        Ok(())
+    }
+
+    /// 通过带 a_bogus 签名的 `webcast/room/web/enter` 接口获取房间信息
+    async fn fetch_room_info_by_api(&self) -> LsarResult<RoomInfo> {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("aid", "6383")
+            .append_pair("app_name", "douyin_web")
+            .append_pair("live_id", "1")
+            .append_pair("device_platform", "web")
+            .append_pair("language", "zh-CN")
+            .append_pair("enter_from", "web_live")
+            .append_pair("cookie_enabled", "true")
+            .append_pair("screen_width", "1920")
+            .append_pair("screen_height", "1080")
+            .append_pair("browser_language", "zh-CN")
+            .append_pair("browser_platform", BROWSER_PLATFORM)
+            .append_pair("browser_name", "Chrome")
+            .append_pair("browser_version", "90.0.4430.212")
+            .append_pair("web_rid", &self.room_id.to_string())
+            .append_pair("msToken", &generate_ms_token())
+            .finish();
+
+        let a_bogus = ABogusGenerator::new(Some(BROWSER_PLATFORM))
+            .generate_a_bogus(&query, None, None, None, None, None);
+
+        let mut url = Url::parse(ROOM_ENTER_API)?;
+        url.set_query(Some(&query));
+        url.query_pairs_mut().append_pair("a_bogus", &a_bogus);
+        debug!("Fetching signed room info: {}", url);
+
+        let body: Value = self
+            .client
+            .get_json(url.as_str(), self.headers.clone())
+            .await?;
+        trace!("Room enter response: {}", body);
+
+        let status_code = body["status_code"].as_i64().unwrap_or(-1);
+        if status_code != 0 {
+            let message = body["data"]["prompts"]
+                .as_str()
+                .unwrap_or("Unknown error")
+                .to_string();
+            error!("Room enter API failed ({}): {}", status_code, message);
+            return Err(LsarError::Other(message));
+        }
+
+        let data = &body["data"];
+        if data["user"].is_null() {
+            return Err(RoomStateError::NotExists.into());
+        }
+
+        if data["data"].as_array().is_none_or(|rooms| rooms.is_empty()) {
+            return Err(RoomStateError::Offline.into());
+        }
+
+        let room_info: RoomInfo = serde_json::from_value(body)?;
+        info!("Room info: {:?}", room_info);
+
+        Ok(room_info)
+    }
 
     /// 获取房间信息
     async fn fetch_room_info(&self) -> LsarResult<RoomInfo> {
-        debug!("Fetching room page: {}", self.room_url);
+        let room_url = self.room_url();
+        debug!("Fetching room page: {}", room_url);
 
-        let body = self.client.get_text(&self.room_url).await?;
+        let body = self.client.get_text(&room_url).await?;
         let state_regex = Regex::new(r#"\{\\"state\\":(.+?\}),\\"children\\":"#)?;
         let state_match = state_regex
             .captures(&body)
@@ -101,7 +199,7 @@ impl DouyinParser {
     fn extract_parsed_result(&self, room_info: RoomInfo) -> LsarResult<ParsedResult> {
         trace!("Extracting parsed result from room info");
 
-        let room_data = &room_info.data.data[0];
+        let room_data = room_info.data.data.first().ok_or(RoomStateError::Offline)?;
         let user_info = &room_info.data.user;
         let partition_info = &room_info.data.partition_road_map;
 
@@ -121,7 +219,11 @@ impl DouyinParser {
             anchor: user_info.nickname.clone(),
             title: room_data.title.clone(),
             links: stream_urls,
-            room_id: self.room_id,
+            qualities: Vec::new(),
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+            room_id: self.room_id.to_string(),
             category,
         };
 
@@ -130,7 +232,7 @@ impl DouyinParser {
     }
 
     /// 提取流媒体 URL
-    fn extract_stream_urls(&self, room_data: &StreamData) -> LsarResult<Vec<String>> {
+    fn extract_stream_urls(&self, room_data: &StreamData) -> LsarResult<Vec<Link>> {
         let stream_url = room_data
             .stream_url
             .as_ref()
@@ -145,8 +247,8 @@ impl DouyinParser {
         debug!("HLS URL available: {}", hls_url.is_some());
 
         Ok(vec![
-            flv_url.unwrap_or_default(),
-            hls_url.unwrap_or_default(),
+            Link::new(flv_url.unwrap_or_default()).format("FLV"),
+            Link::new(hls_url.unwrap_or_default()).format("HLS"),
         ])
     }
 
@@ -181,17 +283,32 @@ impl Parser for DouyinParser {
     async fn parse(&mut self) -> LsarResult<ParsedResult> {
         info!("Starting parse process for Douyin room {}", self.room_id);
 
-        // self.configure_request_headers().await?;
-        let room_info = self.fetch_room_info().await?;
+        self.resolve_room_id().await?;
+
+        let signed = match self.configure_request_headers().await {
+            Ok(()) => self.fetch_room_info_by_api().await,
+            Err(e) => Err(e),
+        };
+
+        // 房间状态是确定的结果，只有接口本身失败时才回退到解析网页
+        let room_info = match signed {
+            Ok(room_info) => room_info,
+            Err(LsarError::RoomState(e)) => return Err(e.into()),
+            Err(e) => {
+                warn!("Signed web API failed, falling back to room page: {}", e);
+                self.fetch_room_info().await?
+            }
+        };
+
         self.extract_parsed_result(room_info)
     }
 }
 
 #[tauri::command]
-pub async fn parse_douyin(room_id: u64) -> LsarResult<ParsedResult> {
-    info!("Parsing Douyin stream for room {}", room_id);
+pub async fn parse_douyin(room_id: u64, url: Option<String>) -> LsarResult<ParsedResult> {
+    info!("Parsing Douyin stream for room {}, url: {:?}", room_id, url);
 
-    let mut parser = DouyinParser::new(room_id);
+    let mut parser = DouyinParser::new(room_id, url);
     let result = parser.parse().await;
 
     match &result {
@@ -201,3 +318,10 @@ pub async fn parse_douyin(room_id: u64) -> LsarResult<ParsedResult> {
 
     result
 }
+
+fn header_value(value: &str) -> LsarResult<HeaderValue> {
+    HeaderValue::from_str(value).map_err(|e| {
+        error!("Failed to create header value: {}", e);
+        LsarError::from(e.to_string())
+    })
+}
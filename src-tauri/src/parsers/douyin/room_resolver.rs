@@ -0,0 +1,234 @@
+use regex::Regex;
+use serde_json::Value;
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
+use crate::network::http::Client;
+
+const REFLOW_INFO_URL: &str = "https://webcast.amemv.com/webcast/room/reflow/info/";
+const USER_PAGE_BASE_URL: &str = "https://www.douyin.com/user/";
+
+/// 抖音用户可能输入的各种链接形式
+#[derive(Debug, PartialEq, Eq)]
+enum DouyinUrl {
+    /// `live.douyin.com/<web_rid>`，路径即为网页端房间号
+    WebRid(u64),
+    /// `v.douyin.com/xxxx` 分享短链，需要跟随重定向
+    ShortLink,
+    /// `douyin.com/user/<sec_uid>` 或 `iesdouyin.com/share/user/<sec_uid>` 用户主页
+    User(String),
+    /// `webcast.amemv.com` 回流链接，其中的 `room_id` 与 `web_rid` 不同
+    Reflow {
+        room_id: u64,
+        sec_uid: Option<String>,
+    },
+}
+
+impl DouyinUrl {
+    fn classify(url: &Url) -> LsarResult<Self> {
+        let host = url.host_str().unwrap_or_default();
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|s| s.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let query = |key: &str| {
+            url.query_pairs()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.into_owned())
+        };
+
+        match host {
+            "live.douyin.com" => segments
+                .first()
+                .and_then(|s| s.parse().ok())
+                .map(DouyinUrl::WebRid)
+                .ok_or_else(|| MissKeyFieldError::RoomId.into()),
+            "v.douyin.com" => Ok(DouyinUrl::ShortLink),
+            "webcast.amemv.com" => {
+                let room_id = query("room_id")
+                    .or_else(|| segments.last().map(|s| s.to_string()))
+                    .and_then(|s| s.parse().ok())
+                    .ok_or(MissKeyFieldError::RoomId)?;
+
+                Ok(DouyinUrl::Reflow {
+                    room_id,
+                    sec_uid: query("sec_user_id"),
+                })
+            }
+            "www.douyin.com" | "douyin.com" | "www.iesdouyin.com" | "iesdouyin.com" => {
+                match segments.iter().position(|s| *s == "user") {
+                    Some(i) if i + 1 < segments.len() => {
+                        Ok(DouyinUrl::User(segments[i + 1].to_string()))
+                    }
+                    _ => Err(MissKeyFieldError::RoomId.into()),
+                }
+            }
+            _ => {
+                error!("Unsupported Douyin URL: {}", url);
+                Err(LsarError::Other(format!("不支持的抖音链接: {}", url)))
+            }
+        }
+    }
+}
+
+/// 将各种形式的抖音链接解析为网页端房间号（web_rid）
+pub(super) struct RoomResolver<'a> {
+    client: &'a Client,
+}
+
+impl<'a> RoomResolver<'a> {
+    pub(super) fn new(client: &'a Client) -> Self {
+        RoomResolver { client }
+    }
+
+    pub(super) async fn resolve(&self, input: &str) -> LsarResult<u64> {
+        debug!("Resolving Douyin web_rid from: {}", input);
+        let url = Url::parse(input)?;
+
+        let web_rid = match DouyinUrl::classify(&url)? {
+            DouyinUrl::WebRid(web_rid) => web_rid,
+            DouyinUrl::ShortLink => {
+                let response = self.client.get(input).await?;
+                let final_url = response.url().clone();
+                debug!("Short link redirected to: {}", final_url);
+
+                match DouyinUrl::classify(&final_url)? {
+                    DouyinUrl::WebRid(web_rid) => web_rid,
+                    DouyinUrl::User(sec_uid) => self.resolve_user(&sec_uid).await?,
+                    DouyinUrl::Reflow { room_id, sec_uid } => {
+                        self.resolve_reflow(room_id, sec_uid.as_deref()).await?
+                    }
+                    DouyinUrl::ShortLink => {
+                        error!("Short link did not redirect: {}", final_url);
+                        return Err(MissKeyFieldError::RoomId.into());
+                    }
+                }
+            }
+            DouyinUrl::User(sec_uid) => self.resolve_user(&sec_uid).await?,
+            DouyinUrl::Reflow { room_id, sec_uid } => {
+                self.resolve_reflow(room_id, sec_uid.as_deref()).await?
+            }
+        };
+
+        info!("Resolved Douyin web_rid: {}", web_rid);
+        Ok(web_rid)
+    }
+
+    /// 从用户主页中找到正在直播的房间，未开播的用户主页中没有 `roomIdStr`
+    async fn resolve_user(&self, sec_uid: &str) -> LsarResult<u64> {
+        debug!("Resolving live room of user: {}", sec_uid);
+        let html = self
+            .client
+            .get_text(&format!("{}{}", USER_PAGE_BASE_URL, sec_uid))
+            .await?;
+
+        let room_id = extract_user_room_id(&html)?.ok_or_else(|| {
+            info!("User {} is not live", sec_uid);
+            RoomStateError::Offline
+        })?;
+
+        self.resolve_reflow(room_id, Some(sec_uid)).await
+    }
+
+    /// 通过回流接口将 `room_id` 转换为 `web_rid`
+    async fn resolve_reflow(&self, room_id: u64, sec_uid: Option<&str>) -> LsarResult<u64> {
+        debug!("Resolving web_rid from room_id: {}", room_id);
+        let mut url = Url::parse(REFLOW_INFO_URL)?;
+        url.query_pairs_mut()
+            .append_pair("type_id", "0")
+            .append_pair("live_id", "1")
+            .append_pair("room_id", &room_id.to_string())
+            .append_pair("sec_user_id", sec_uid.unwrap_or_default())
+            .append_pair("version_code", "99.99.99")
+            .append_pair("app_id", "1128");
+
+        let body: Value = self.client.get_json(url.as_str(), None).await?;
+        trace!("Reflow info response: {}", body);
+
+        let room = &body["data"]["room"];
+        if room.is_null() {
+            return Err(RoomStateError::NotExists.into());
+        }
+
+        // 未开播时 status 为 4
+        if room["status"].as_i64() == Some(4) {
+            return Err(RoomStateError::Offline.into());
+        }
+
+        room["owner"]["web_rid"]
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| {
+                error!("Failed to find web_rid in reflow info");
+                MissKeyFieldError::RoomId.into()
+            })
+    }
+}
+
+fn extract_user_room_id(html: &str) -> LsarResult<Option<u64>> {
+    let re = Regex::new(r#"roomIdStr\\?":\\?"(\d+)"#)?;
+
+    Ok(re
+        .captures(html)
+        .and_then(|caps| caps[1].parse().ok())
+        .filter(|room_id| *room_id != 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(url: &str) -> DouyinUrl {
+        DouyinUrl::classify(&Url::parse(url).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_classify_web_rid() {
+        assert_eq!(
+            classify("https://live.douyin.com/745964462470?from=search"),
+            DouyinUrl::WebRid(745964462470)
+        );
+    }
+
+    #[test]
+    fn test_classify_short_link() {
+        assert_eq!(
+            classify("https://v.douyin.com/iRNBho6u/"),
+            DouyinUrl::ShortLink
+        );
+    }
+
+    #[test]
+    fn test_classify_user() {
+        assert_eq!(
+            classify("https://www.douyin.com/user/MS4wLjABAAAA8U_l6rBzmy7bcy6xOJel4v0RzoR_wfAubGPeJimN__4?vid=1"),
+            DouyinUrl::User("MS4wLjABAAAA8U_l6rBzmy7bcy6xOJel4v0RzoR_wfAubGPeJimN__4".to_string())
+        );
+        assert_eq!(
+            classify("https://www.iesdouyin.com/share/user/MS4wLjABAAAA?u_code=1"),
+            DouyinUrl::User("MS4wLjABAAAA".to_string())
+        );
+    }
+
+    #[test]
+    fn test_classify_reflow() {
+        assert_eq!(
+            classify("https://webcast.amemv.com/douyin/webcast/reflow/7350810109514648371?u_code=l1j9bkbd&sec_user_id=MS4wLjABAAAA"),
+            DouyinUrl::Reflow {
+                room_id: 7350810109514648371,
+                sec_uid: Some("MS4wLjABAAAA".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_user_room_id() {
+        let html =
+            r#"self.__pace_f.push([1,"{\"roomIdStr\":\"7350810109514648371\",\"roomData\""])"#;
+        assert_eq!(
+            extract_user_room_id(html).unwrap(),
+            Some(7350810109514648371)
+        );
+        assert_eq!(extract_user_room_id(r#"{"roomIdStr":"0"}"#).unwrap(), None);
+    }
+}
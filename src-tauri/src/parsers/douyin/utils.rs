@@ -1,28 +1,36 @@
 use regex::Regex;
+use reqwest::header::{HeaderMap, SET_COOKIE};
 
 use crate::error::LsarResult;
 use crate::network::http::Client;
 
-pub async fn get_set_cookie(client: &Client, url: &str) -> LsarResult<String> {
+pub async fn get_set_cookie(client: &Client, url: &str, headers: &HeaderMap) -> LsarResult<String> {
     trace!("Entering get_set_cookie function");
-    let resp = client.get(url).await?;
+    let resp = client
+        .send_request(client.inner.get(url), Some(headers.clone()))
+        .await?;
+
+    // 响应中可能有多个 Set-Cookie 头，需要全部取出
     let cookies = resp
         .headers()
-        .get("set-cookie")
-        .ok_or_else(|| {
-            error!("Set-Cookie header not found in response");
-            "Set-Cookie header not found"
-        })?
-        .to_str()
-        .unwrap()
-        .to_string();
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    if cookies.is_empty() {
+        error!("Set-Cookie header not found in response");
+        return Err("Set-Cookie header not found".into());
+    }
+
     debug!("Set-Cookie header fetched successfully: {}", cookies);
     Ok(cookies)
 }
 
-pub async fn get_ac_nonce(client: &Client, url: &str) -> LsarResult<String> {
+pub async fn get_ac_nonce(client: &Client, url: &str, headers: &HeaderMap) -> LsarResult<String> {
     trace!("Entering get_ac_nonce function");
-    let cookies = get_set_cookie(client, url).await?;
+    let cookies = get_set_cookie(client, url, headers).await?;
 
     trace!("Extracting __ac_nonce from cookies");
     let re = Regex::new(r"__ac_nonce=(.*?);")?;
@@ -43,9 +51,9 @@ pub async fn get_ac_nonce(client: &Client, url: &str) -> LsarResult<String> {
     Ok(ac_nonce)
 }
 
-pub async fn get_ttwid(client: &Client, url: &str) -> LsarResult<String> {
+pub async fn get_ttwid(client: &Client, url: &str, headers: &HeaderMap) -> LsarResult<String> {
     trace!("Entering get_ttwid function");
-    let cookies = get_set_cookie(client, url).await?;
+    let cookies = get_set_cookie(client, url, headers).await?;
     let re = Regex::new(r"ttwid=(.*?);")?;
     let ttwid = re
         .captures(&cookies)
@@ -2,7 +2,7 @@ use regex::Regex;
 
 use super::models::RoomInfo;
 use crate::error::{LsarResult, MissKeyFieldError, RoomStateError};
-use crate::parsers::ParsedResult;
+use crate::parsers::{Link, ParsedResult};
 use crate::platform::Platform;
 
 pub struct StreamInfoParser {}
@@ -52,9 +52,13 @@ impl StreamInfoParser {
             platform: Platform::Douyu,
             title: self.parse_stream_title(html)?,
             anchor: self.parse_anchor_name(html)?,
-            room_id: self.extract_final_room_id(html)?,
+            room_id: self.extract_final_room_id(html)?.to_string(),
             category: self.parse_stream_category(html),
-            links: vec![stream_url],
+            links: vec![Link::new(stream_url).format("FLV")],
+            qualities: Vec::new(),
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
         };
 
         info!("Stream info parsed successfully");
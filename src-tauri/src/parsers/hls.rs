@@ -0,0 +1,217 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use url::Url;
+
+use crate::error::{LsarError, LsarResult};
+
+use super::{Link, Quality};
+
+/// HLS 主播放列表中的一个变体流
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Variant {
+    pub url: String,
+    pub bandwidth: u64,
+    /// 如 `1920x1080`
+    pub resolution: Option<String>,
+    pub frame_rate: Option<f64>,
+    pub codecs: Option<String>,
+    /// 来自 `VIDEO` 分组对应的 `#EXT-X-MEDIA` 的 `NAME`，Twitch 等平台用它标注画质
+    pub name: Option<String>,
+}
+
+impl Variant {
+    /// 画质名称，优先使用播放列表中的名称，否则由分辨率和帧率生成，如 `1080p60`
+    pub fn label(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+
+        let height = self
+            .resolution
+            .as_deref()
+            .and_then(|r| r.split_once('x'))
+            .map(|(_, h)| h);
+        match (height, self.frame_rate) {
+            (Some(h), Some(fps)) if fps > 30.5 => format!("{}p{}", h, fps.round()),
+            (Some(h), _) => format!("{}p", h),
+            _ => format!("{} kbps", self.bandwidth / 1000),
+        }
+    }
+
+    /// 视频编码，取 `CODECS` 中第一个视频编码的简称
+    pub fn codec(&self) -> Option<&'static str> {
+        self.codecs.as_deref()?.split(',').find_map(|c| {
+            let c = c.trim();
+            if c.starts_with("avc1") || c.starts_with("avc3") {
+                Some("avc")
+            } else if c.starts_with("hvc1") || c.starts_with("hev1") {
+                Some("hevc")
+            } else if c.starts_with("av01") {
+                Some("av1")
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// 解析主播放列表，按码率从高到低排序，相对地址基于 `base` 解析
+pub(super) fn parse_master_playlist(base: &Url, text: &str) -> LsarResult<Vec<Variant>> {
+    if !text.trim_start().starts_with("#EXTM3U") {
+        // 错误页面可能很长，只记录开头
+        let prefix: String = text.chars().take(100).collect();
+        error!(
+            "Not a m3u8 playlist ({} bytes), starts with: {:?}",
+            text.len(),
+            prefix
+        );
+        return Err(LsarError::from("Invalid m3u8 playlist"));
+    }
+
+    // VIDEO 分组 ID 到名称的映射
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut variants = Vec::new();
+    let mut pending: Option<HashMap<String, String>> = None;
+
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(attrs) = line.strip_prefix("#EXT-X-MEDIA:") {
+            let attrs = parse_attributes(attrs);
+            if attrs.get("TYPE").map(String::as_str) == Some("VIDEO") {
+                if let (Some(group), Some(name)) = (attrs.get("GROUP-ID"), attrs.get("NAME")) {
+                    names.insert(group.clone(), name.clone());
+                }
+            }
+        } else if let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+            pending = Some(parse_attributes(attrs));
+        } else if !line.starts_with('#') {
+            let Some(attrs) = pending.take() else {
+                continue;
+            };
+
+            variants.push(Variant {
+                url: base.join(line)?.to_string(),
+                bandwidth: attrs
+                    .get("BANDWIDTH")
+                    .and_then(|b| b.parse().ok())
+                    .unwrap_or_default(),
+                resolution: attrs.get("RESOLUTION").cloned(),
+                frame_rate: attrs.get("FRAME-RATE").and_then(|f| f.parse().ok()),
+                codecs: attrs.get("CODECS").cloned(),
+                name: attrs.get("VIDEO").and_then(|g| names.get(g)).cloned(),
+            });
+        }
+    }
+
+    variants.sort_by_key(|v| Reverse(v.bandwidth));
+    debug!("Parsed {} variants from master playlist", variants.len());
+
+    Ok(variants)
+}
+
+/// 每个变体一个链接，画质值为码率
+pub(super) fn into_links(variants: Vec<Variant>) -> (Vec<Link>, Vec<Quality>) {
+    let qualities = variants
+        .iter()
+        .map(|v| Quality::new(v.bandwidth as i64, v.label()))
+        .collect();
+
+    let links = variants
+        .into_iter()
+        .map(|v| {
+            let (label, codec) = (v.label(), v.codec());
+            let link = Link::new(v.url).quality(label).format("HLS");
+            match codec {
+                Some(codec) => link.codec(codec),
+                None => link,
+            }
+        })
+        .collect();
+
+    (links, qualities)
+}
+
+/// 解析 `KEY=VALUE,KEY="VALUE,WITH,COMMA"` 形式的属性列表
+fn parse_attributes(input: &str) -> HashMap<String, String> {
+    let mut attrs = HashMap::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let Some((key, value)) = rest.split_once('=') else {
+            break;
+        };
+
+        let (value, remain) = match value.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, remain)) => (value, remain),
+                None => (quoted, ""),
+            },
+            None => value.split_once(',').map_or((value, ""), |(v, r)| (v, r)),
+        };
+
+        attrs.insert(key.trim().to_string(), value.to_string());
+        rest = remain.trim_start_matches(',');
+    }
+
+    attrs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYLIST: &str = r#"#EXTM3U
+#EXT-X-TWITCH-INFO:NODE="video-edge",CLUSTER="sea"
+#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID="chunked",NAME="1080p60 (source)",AUTOSELECT=YES,DEFAULT=YES
+#EXT-X-STREAM-INF:BANDWIDTH=8534030,RESOLUTION=1920x1080,CODECS="avc1.64002A,mp4a.40.2",VIDEO="chunked",FRAME-RATE=60.000
+https://video-weaver.sea.hls.ttvnw.net/v1/playlist/source.m3u8
+#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID="720p30",NAME="720p",AUTOSELECT=YES,DEFAULT=YES
+#EXT-X-STREAM-INF:BANDWIDTH=2373000,RESOLUTION=1280x720,CODECS="avc1.4D401F,mp4a.40.2",VIDEO="720p30",FRAME-RATE=30.000
+https://video-weaver.sea.hls.ttvnw.net/v1/playlist/720p.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=160000,CODECS="mp4a.40.2"
+audio_only/index.m3u8
+"#;
+
+    #[test]
+    fn test_parse_master_playlist() {
+        let base = Url::parse("https://example.com/live/master.m3u8").unwrap();
+        let variants = parse_master_playlist(&base, PLAYLIST).unwrap();
+
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants[0].label(), "1080p60 (source)");
+        assert_eq!(variants[0].codec(), Some("avc"));
+        assert_eq!(variants[0].frame_rate, Some(60.0));
+        assert_eq!(variants[1].label(), "720p");
+        assert_eq!(
+            variants[2].url,
+            "https://example.com/live/audio_only/index.m3u8"
+        );
+        assert_eq!(variants[2].label(), "160 kbps");
+        assert_eq!(variants[2].codec(), None);
+
+        assert!(parse_master_playlist(&base, "<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_label_from_resolution() {
+        let variant = Variant {
+            url: String::new(),
+            bandwidth: 0,
+            resolution: Some("1920x1080".to_string()),
+            frame_rate: Some(59.94),
+            codecs: Some("hvc1.1.6.L120.90,mp4a.40.2".to_string()),
+            name: None,
+        };
+        assert_eq!(variant.label(), "1080p60");
+        assert_eq!(variant.codec(), Some("hevc"));
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        let attrs =
+            parse_attributes(r#"BANDWIDTH=1,CODECS="avc1.4D401F,mp4a.40.2",VIDEO="720p30""#);
+        assert_eq!(attrs["BANDWIDTH"], "1");
+        assert_eq!(attrs["CODECS"], "avc1.4D401F,mp4a.40.2");
+        assert_eq!(attrs["VIDEO"], "720p30");
+    }
+}
@@ -1,6 +1,13 @@
+use std::time::Duration;
+
 use base64::{prelude::BASE64_STANDARD, Engine};
+use url::{form_urlencoded, Url};
+
+use crate::error::{LsarError, LsarResult};
+use crate::utils::{md5, now};
 
-use crate::error::LsarResult;
+use super::constants::{SDK_VERSION, STRIPPED_PARAMS};
+use super::url::UrlParser;
 
 pub(super) struct AnticodeParser;
 
@@ -13,11 +20,122 @@ impl AnticodeParser {
         ws_time: &str,
     ) -> LsarResult<String> {
         trace!("Parsing FM");
-        Ok(String::from_utf8(BASE64_STANDARD.decode(fm).unwrap())
-            .unwrap()
+        let decoded = BASE64_STANDARD
+            .decode(fm)
+            .map_err(|e| LsarError::Other(format!("Invalid fm: {}", e)))?;
+
+        Ok(String::from_utf8(decoded)
+            .map_err(|e| LsarError::Other(format!("Invalid fm: {}", e)))?
             .replace("$0", uid)
             .replace("$1", stream_name)
             .replace("$2", ss)
             .replace("$3", ws_time))
     }
+
+    /// 对防盗链参数签名，相同的输入总是得到相同的查询字符串
+    ///
+    /// 保留原参数的顺序，替换 `wsSecret`，移除 `fm`、`txyp`，
+    /// 然后依次追加 `ver`、`sv`、`seqid`、`uid`、`uuid`。
+    pub(super) fn sign(
+        code: &str,
+        stream_name: &str,
+        uid: &str,
+        now_millis: u128,
+        uuid: u128,
+    ) -> LsarResult<String> {
+        debug!("Signing anticode for stream: {}", stream_name);
+        let query = UrlParser::parse_query(code)?;
+        let param = |key: &str| {
+            query
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+                .ok_or_else(|| LsarError::Other(format!("Anticode missing `{}`", key)))
+        };
+
+        let seq_id = (uid.parse::<u128>()? + now_millis).to_string();
+        let ss = md5(format!("{}|{}|{}", seq_id, param("ctype")?, param("t")?));
+        let fm = Self::parse_fm(param("fm")?, uid, stream_name, &ss, param("wsTime")?)?;
+        let ws_secret = md5(fm);
+        debug!("Generated seqid: {}, wsSecret: {}", seq_id, ws_secret);
+
+        let appended = [
+            ("ver", "1".to_string()),
+            ("sv", SDK_VERSION.to_string()),
+            ("seqid", seq_id),
+            ("uid", uid.to_string()),
+            ("uuid", uuid.to_string()),
+        ];
+
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+        for (k, v) in &query {
+            if STRIPPED_PARAMS.contains(&k.as_str()) || appended.iter().any(|(key, _)| key == k) {
+                continue;
+            }
+
+            let value = if k == "wsSecret" { &ws_secret } else { v };
+            serializer.append_pair(k, value);
+        }
+
+        if !query.iter().any(|(k, _)| k == "wsSecret") {
+            serializer.append_pair("wsSecret", &ws_secret);
+        }
+
+        for (k, v) in &appended {
+            serializer.append_pair(k, v);
+        }
+
+        Ok(serializer.finish())
+    }
+
+    /// 计算已签名链接的剩余有效期，`wsTime` 是十六进制的过期时间戳（秒）
+    pub(super) fn remaining_lifetime(link: &str) -> LsarResult<Duration> {
+        let url = Url::parse(link)?;
+        let ws_time = url
+            .query_pairs()
+            .find(|(k, _)| k == "wsTime")
+            .map(|(_, v)| v.into_owned())
+            .ok_or_else(|| LsarError::Other("Link missing `wsTime`".to_string()))?;
+
+        let expires_at = u64::from_str_radix(&ws_time, 16)?;
+        let remaining = expires_at.saturating_sub(now()?.as_secs());
+        debug!("Link expires at {}, {}s remaining", expires_at, remaining);
+
+        Ok(Duration::from_secs(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANTICODE: &str = "wsSecret=00000000000000000000000000000000&wsTime=66f0a1b2&fm=RFdxOEJjSjNoNkRKdDZUWV8kMF8kMV8kMl8kMw%3D%3D&ctype=huya_live&fs=bgct&t=100";
+    const STREAM_NAME: &str =
+        "1199561367042-1199561367042-5484893683376832512-2399122857540-10057-A-0-1";
+
+    #[test]
+    fn test_sign_is_deterministic() {
+        let signed =
+            AnticodeParser::sign(ANTICODE, STREAM_NAME, "1463993859134", 1718000000000, 42)
+                .unwrap();
+
+        assert_eq!(
+            signed,
+            "wsSecret=e3ce837875e2aae84c20e1c156e22c0f&wsTime=66f0a1b2&ctype=huya_live&fs=bgct&t=100&ver=1&sv=2110211124&seqid=3181993859134&uid=1463993859134&uuid=42"
+        );
+    }
+
+    #[test]
+    fn test_remaining_lifetime() {
+        let expired = "https://al.flv.huya.com/src/a.flv?wsSecret=x&wsTime=5f5e1000";
+        assert_eq!(
+            AnticodeParser::remaining_lifetime(expired).unwrap(),
+            Duration::ZERO
+        );
+
+        let ws_time = format!("{:x}", now().unwrap().as_secs() + 3600);
+        let valid = format!("https://al.flv.huya.com/src/a.flv?wsTime={}", ws_time);
+        let remaining = AnticodeParser::remaining_lifetime(&valid).unwrap();
+        assert!(remaining > Duration::from_secs(3590) && remaining <= Duration::from_secs(3600));
+    }
 }
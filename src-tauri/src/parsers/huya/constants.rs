@@ -0,0 +1,25 @@
+/// 播放器 SDK 版本，对应链接中的 `sv` 参数
+pub(super) const SDK_VERSION: &str = "2110211124";
+
+/// 签名时从防盗链参数中移除的字段
+pub(super) const STRIPPED_PARAMS: [&str; 2] = ["fm", "txyp"];
+
+/// 距离 `wsTime` 不足此秒数时认为链接即将过期，应重新签名
+pub(super) const REFRESH_THRESHOLD_SECS: u64 = 60;
+
+/// 原画对应的码率，请求原画时不需要 `ratio` 参数
+pub(super) const ORIGINAL_BIT_RATE: u32 = 0;
+
+/// 将 `sCdnType` 转换为可读的 CDN 名称，未知的类型原样返回
+pub(super) fn cdn_name(cdn_type: &str) -> &str {
+    match cdn_type {
+        "AL" => "阿里云",
+        "TX" => "腾讯云",
+        "HW" => "华为云",
+        "HS" => "火山引擎",
+        "WS" => "网宿",
+        "BD" => "百度云",
+        "HYZJ" => "虎牙自建",
+        other => other,
+    }
+}
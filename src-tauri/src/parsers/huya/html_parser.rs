@@ -1,4 +1,7 @@
-use crate::error::{LsarError, LsarResult};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError};
 
 pub(super) struct HtmlParser;
 
@@ -11,4 +14,60 @@ impl HtmlParser {
             .map(|(json, _)| format!("{}}}", json))
             .ok_or_else(|| LsarError::from("Failed to extract stream info"))
     }
+
+    /// 从房间页面中提取数字房间号 `profileRoom`
+    ///
+    /// 优先使用播放器配置中的流信息，未开播或赛事等靓号页面没有流信息时，
+    /// 依次尝试 `TT_ROOM_DATA` 和手机端页面的 `lProfileRoom`。
+    pub(super) fn extract_profile_room(html: &str) -> LsarResult<u64> {
+        trace!("Extracting profile room from HTML");
+
+        if let Ok(stream_str) = Self::extract_stream_info(html) {
+            debug!("Extracted stream info: {}", stream_str);
+            let stream: Value = serde_json::from_str(&stream_str)?;
+            if let Some(room_id) = stream["data"][0]["gameLiveInfo"]["profileRoom"].as_u64() {
+                return Ok(room_id);
+            }
+        }
+
+        let re = Regex::new(r#""l?[pP]rofileRoom"\s*:\s*"?(\d+)"#)?;
+        let room_id = re
+            .captures_iter(html)
+            .filter_map(|caps| caps[1].parse::<u64>().ok())
+            .find(|room_id| *room_id != 0)
+            .ok_or_else(|| {
+                error!("Failed to extract profile room");
+                MissKeyFieldError::RoomId
+            })?;
+
+        Ok(room_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_profile_room_from_stream() {
+        let html = include_str!("fixtures/desktop_live.html");
+        assert_eq!(HtmlParser::extract_profile_room(html).unwrap(), 11342412);
+    }
+
+    #[test]
+    fn test_extract_profile_room_from_vanity_page() {
+        let html = include_str!("fixtures/vanity.html");
+        assert_eq!(HtmlParser::extract_profile_room(html).unwrap(), 660000);
+    }
+
+    #[test]
+    fn test_extract_profile_room_from_mobile_page() {
+        let html = include_str!("fixtures/mobile.html");
+        assert_eq!(HtmlParser::extract_profile_room(html).unwrap(), 11342412);
+    }
+
+    #[test]
+    fn test_extract_profile_room_not_found() {
+        assert!(HtmlParser::extract_profile_room("<html></html>").is_err());
+    }
 }
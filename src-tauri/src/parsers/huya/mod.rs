@@ -1,35 +1,58 @@
 mod anticode_parser;
+mod constants;
 mod html_parser;
 mod login_request;
 mod models;
+mod room_resolver;
 mod url;
 mod uuid;
 
+use serde::Serialize;
 use serde_json::Value;
-use url::UrlParser;
 
 use self::anticode_parser::AnticodeParser;
+use self::constants::{cdn_name, ORIGINAL_BIT_RATE, REFRESH_THRESHOLD_SECS};
 use self::html_parser::HtmlParser;
 use self::login_request::LoginRequest;
+use self::room_resolver::resolve_page_url;
 use self::uuid::UuidGenerator;
 
 use crate::network::http::Client;
 use crate::utils::now;
 use crate::{
-    error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError},
+    error::{LsarError, LsarResult, RoomStateError},
     platform::Platform,
-    utils::md5,
 };
 
-use super::{ParsedResult, Parser};
+use super::{Link, ParsedResult, Parser, Quality};
 
-use self::models::{BaseSteamInfo, CacheProfile};
+use self::models::{BaseSteamInfo, BitRateInfo, CacheProfile};
 
 const BASE_URL: &str = "https://www.huya.com/";
 
+/// 重新签名后的链接
+#[derive(Debug, Serialize)]
+pub struct RefreshedLink {
+    url: String,
+    /// 剩余有效期（秒）
+    expires_in: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkLifetime {
+    /// 剩余有效期（秒）
+    expires_in: u64,
+    /// 即将过期，应调用 `refresh_huya_link` 重新签名
+    should_refresh: bool,
+}
+
 struct HuyaParser {
     room_id: Option<u64>,
     page_url: String,
+    /// 码率，0 或 None 为原画
+    ratio: Option<u32>,
+    /// 优先使用的 CDN，对应 `sCdnType`，如 `AL`、`TX`
+    cdn: Option<String>,
     client: Client,
 }
 
@@ -40,16 +63,24 @@ impl HuyaParser {
         HuyaParser {
             room_id,
             page_url,
+            ratio: None,
+            cdn: None,
             client,
         }
     }
 
+    fn with_options(mut self, ratio: Option<u32>, cdn: Option<String>) -> Self {
+        self.ratio = ratio;
+        self.cdn = cdn;
+        self
+    }
+
     async fn get_final_room_id(&self) -> LsarResult<u64> {
         info!("Fetching final room ID");
         let url = if let Some(id) = self.room_id {
             format!("{}{}", BASE_URL, id)
         } else {
-            self.page_url.clone()
+            resolve_page_url(&self.page_url)?
         };
 
         debug!("Fetching HTML from URL: {}", url);
@@ -58,23 +89,7 @@ impl HuyaParser {
             e
         })?;
 
-        let stream_str = HtmlParser::extract_stream_info(&html).map_err(|e| {
-            error!("Failed to extract stream info: {}", e);
-            e
-        })?;
-        debug!("Extracted stream info: {}", stream_str);
-
-        let stream: Value = serde_json::from_str(&stream_str).map_err(|e| {
-            error!("Failed to parse stream info JSON: {}", e);
-            e
-        })?;
-
-        let room_id = stream["data"][0]["gameLiveInfo"]["profileRoom"]
-            .as_u64()
-            .ok_or_else(|| {
-                error!("Failed to extract room ID");
-                MissKeyFieldError::RoomId
-            })?;
+        let room_id = HtmlParser::extract_profile_room(&html)?;
 
         info!("Real room ID: {}", room_id);
         Ok(room_id)
@@ -114,40 +129,121 @@ impl HuyaParser {
         Ok(profile_value)
     }
 
-    async fn process_profile(&self, profile: Value, room_id: u64) -> LsarResult<ParsedResult> {
-        trace!("Processing room profile");
-        let live_status = profile["data"]["liveStatus"].as_str().unwrap();
+    fn check_live_status(profile: &Value) -> LsarResult<()> {
+        trace!("Checking live status");
+        let live_status = profile["data"]["liveStatus"].as_str().unwrap_or_default();
         match live_status {
-            "OFF" => {
-                info!("Room is offline");
-                Err(RoomStateError::Offline.into())
+            "ON" => {
+                info!("Room is online");
+                Ok(())
             }
             "REPLAY" => {
                 info!("Room is in replay mode");
                 Err(RoomStateError::IsReplay.into())
             }
-            "ON" => {
-                info!("Room is online");
-                let profile: CacheProfile = serde_json::from_value(profile)?;
-
-                let uid = self.get_anonymous_uid().await?;
-                let links = self
-                    .get_stream_links(&profile.data.stream.base_steam_info_list, &uid)
-                    .await?;
-
-                Ok(ParsedResult {
-                    platform: Platform::Huya,
-                    links,
-                    title: profile.data.live_data.introduction,
-                    anchor: profile.data.live_data.nick,
-                    room_id,
-                    category: profile.data.live_data.game_full_name,
-                })
+            _ => {
+                info!("Room is offline, live status: {}", live_status);
+                Err(RoomStateError::Offline.into())
             }
-            _ => unreachable!(),
         }
     }
 
+    async fn process_profile(&self, profile: Value, room_id: u64) -> LsarResult<ParsedResult> {
+        trace!("Processing room profile");
+        Self::check_live_status(&profile)?;
+
+        let profile: CacheProfile = serde_json::from_value(profile)?;
+
+        let rate_array = profile
+            .data
+            .stream
+            .flv
+            .as_ref()
+            .map(|flv| flv.rate_array.as_slice())
+            .unwrap_or_default();
+        let quality = self.select_quality(rate_array);
+
+        let uid = self.get_anonymous_uid().await?;
+        let links =
+            self.get_stream_links(&profile.data.stream.base_steam_info_list, &uid, &quality)?;
+
+        let qualities = rate_array
+            .iter()
+            .map(|rate| Quality::new(rate.i_bit_rate as i64, &rate.s_display_name))
+            .collect();
+
+        Ok(ParsedResult {
+            platform: Platform::Huya,
+            links,
+            qualities,
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+            title: profile.data.live_data.introduction,
+            anchor: profile.data.live_data.nick,
+            room_id: room_id.to_string(),
+            category: profile.data.live_data.game_full_name,
+        })
+    }
+
+    /// 用最新的防盗链参数和新的 uid、seqid 重新签名已解析的链接
+    async fn refresh_link(&self, room_id: u64, link: &str) -> LsarResult<RefreshedLink> {
+        info!("Refreshing link for room {}", room_id);
+        let (base, _) = link.split_once('?').unwrap_or((link, ""));
+
+        let profile = self.get_room_profile(room_id).await?;
+        Self::check_live_status(&profile)?;
+        let profile: CacheProfile = serde_json::from_value(profile)?;
+
+        let (item, anticode) = profile
+            .data
+            .stream
+            .base_steam_info_list
+            .iter()
+            .find_map(|item| {
+                let flv = format!(
+                    "{}/{}.{}",
+                    item.s_flv_url, item.s_stream_name, item.s_flv_url_suffix
+                );
+                let hls = format!(
+                    "{}/{}.{}",
+                    item.s_hls_url, item.s_stream_name, item.s_hls_url_suffix
+                );
+
+                if flv == base {
+                    Some((item, &item.s_flv_anti_code))
+                } else if hls == base {
+                    Some((item, &item.s_hls_anti_code))
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| {
+                warn!("Stream of link not found in room profile: {}", base);
+                LsarError::from("直播流已变更，请重新解析")
+            })?;
+
+        // 保留原链接中选择的码率
+        let ratio = ::url::Url::parse(link)?
+            .query_pairs()
+            .find(|(k, _)| k == "ratio")
+            .map(|(_, v)| v.into_owned());
+
+        let uid = self.get_anonymous_uid().await?;
+        let mut url = format!(
+            "{}?{}",
+            base,
+            self.parse_anticode(anticode, &uid, &item.s_stream_name)?
+        );
+        if let Some(ratio) = ratio {
+            url = format!("{}&ratio={}", url, ratio);
+        }
+        let expires_in = AnticodeParser::remaining_lifetime(&url)?.as_secs();
+        debug!("Refreshed link expires in {}s: {}", expires_in, url);
+
+        Ok(RefreshedLink { url, expires_in })
+    }
+
     async fn get_anonymous_uid(&self) -> LsarResult<String> {
         info!("Getting anonymous UID");
         let login_request = LoginRequest::new();
@@ -174,44 +270,79 @@ impl HuyaParser {
         Ok(uid)
     }
 
-    async fn get_stream_links(
+    /// 选择请求的码率，不在可选码率中时回退到原画
+    fn select_quality(&self, rate_array: &[BitRateInfo]) -> Quality {
+        let ratio = self.ratio.unwrap_or(ORIGINAL_BIT_RATE);
+
+        match rate_array.iter().find(|rate| rate.i_bit_rate == ratio) {
+            Some(rate) => Quality::new(rate.i_bit_rate as i64, &rate.s_display_name),
+            None => {
+                if ratio != ORIGINAL_BIT_RATE {
+                    warn!("Bit rate {} is not available, using original", ratio);
+                }
+                Quality::new(ORIGINAL_BIT_RATE as i64, "原画")
+            }
+        }
+    }
+
+    /// 按 CDN 排序：优先使用指定的 CDN，其余按 `iPCPriorityRate` 从高到低
+    fn sort_by_cdn<'a>(&self, base_steam_info_list: &'a [BaseSteamInfo]) -> Vec<&'a BaseSteamInfo> {
+        let mut list: Vec<&BaseSteamInfo> = base_steam_info_list.iter().collect();
+        let preferred = self.cdn.as_deref().unwrap_or_default();
+
+        list.sort_by_key(|item| {
+            (
+                !item.s_cdn_type.eq_ignore_ascii_case(preferred),
+                std::cmp::Reverse(item.i_pc_priority_rate),
+            )
+        });
+
+        list
+    }
+
+    fn get_stream_links(
         &self,
         base_steam_info_list: &[BaseSteamInfo],
         uid: &str,
-    ) -> LsarResult<Vec<String>> {
+        quality: &Quality,
+    ) -> LsarResult<Vec<Link>> {
         info!("Getting stream links");
         let mut links = Vec::new();
 
-        for (index, item) in base_steam_info_list.iter().enumerate() {
-            debug!("Processing steam info item {}", index);
+        let with_ratio = |url: String| {
+            if quality.value == ORIGINAL_BIT_RATE as i64 {
+                url
+            } else {
+                format!("{}&ratio={}", url, quality.value)
+            }
+        };
+
+        for item in self.sort_by_cdn(base_steam_info_list) {
+            debug!("Processing steam info of CDN {}", item.s_cdn_type);
+            let cdn = cdn_name(&item.s_cdn_type);
+
             if !item.s_flv_anti_code.is_empty() {
-                match self
-                    .parse_anticode(&item.s_flv_anti_code, uid, &item.s_stream_name)
-                    .await
-                {
+                match self.parse_anticode(&item.s_flv_anti_code, uid, &item.s_stream_name) {
                     Ok(anticode) => {
-                        let url = format!(
+                        let url = with_ratio(format!(
                             "{}/{}.{}?{}",
                             item.s_flv_url, item.s_stream_name, item.s_flv_url_suffix, anticode
-                        );
+                        ));
                         debug!("Added FLV stream link: {}", url);
-                        links.push(url);
+                        links.push(Link::new(url).quality(&quality.name).cdn(cdn).format("FLV"));
                     }
                     Err(e) => error!("Failed to parse FLV anticode: {}", e),
                 }
             }
             if !item.s_hls_anti_code.is_empty() {
-                match self
-                    .parse_anticode(&item.s_hls_anti_code, uid, &item.s_stream_name)
-                    .await
-                {
+                match self.parse_anticode(&item.s_hls_anti_code, uid, &item.s_stream_name) {
                     Ok(anticode) => {
-                        let url = format!(
+                        let url = with_ratio(format!(
                             "{}/{}.{}?{}",
                             item.s_hls_url, item.s_stream_name, item.s_hls_url_suffix, anticode
-                        );
+                        ));
                         debug!("Added HLS stream link: {}", url);
-                        links.push(url);
+                        links.push(Link::new(url).quality(&quality.name).cdn(cdn).format("HLS"));
                     }
                     Err(e) => error!("Failed to parse HLS anticode: {}", e),
                 }
@@ -225,54 +356,19 @@ impl HuyaParser {
         Ok(links)
     }
 
-    async fn parse_anticode(&self, code: &str, uid: &str, stream_name: &str) -> LsarResult<String> {
+    fn parse_anticode(&self, code: &str, uid: &str, stream_name: &str) -> LsarResult<String> {
         debug!("Parsing anticode for stream: {}", stream_name);
-        let mut query = UrlParser::parse_query(code).map_err(|e| {
-            error!("Failed to parse anticode query: {}", e);
-            e
-        })?;
-
-        query.insert("ver".to_string(), vec!["1".to_string()]);
-        query.insert("sv".to_string(), vec!["2110211124".to_string()]);
-
-        let seq_id = format!("{}", uid.parse::<u128>().unwrap() + now()?.as_millis());
-        query.insert("seqid".to_string(), vec![seq_id.clone()]);
-        debug!("Generated seqid: {}", seq_id);
-
-        query.insert("uid".to_string(), vec![uid.to_string()]);
 
         let uuid = UuidGenerator::new_uuid().map_err(|e| {
             error!("Failed to generate UUID: {}", e);
             e
         })?;
-        query.insert("uuid".to_string(), vec![uuid.to_string()]);
         debug!("Generated uuid: {}", uuid);
 
-        let ss = md5(format!(
-            "{}|{}|{}",
-            seq_id, query["ctype"][0], query["t"][0]
-        ));
-        debug!("Generated ss: {}", ss);
-
-        let fm =
-            AnticodeParser::parse_fm(&query["fm"][0], uid, stream_name, &ss, &query["wsTime"][0])
-                .map_err(|e| {
-                error!("Failed to parse FM: {}", e);
-                e
-            })?;
-
-        let ws_secret = md5(fm);
-        query.insert("wsSecret".to_string(), vec![ws_secret.clone()]);
-        debug!("Generated wsSecret: {}", ws_secret);
-
-        query.remove("fm");
-        query.remove("txyp");
-
-        Ok(query
-            .into_iter()
-            .map(|(k, v)| format!("{}={}", k, v[0]))
-            .collect::<Vec<String>>()
-            .join("&"))
+        AnticodeParser::sign(code, stream_name, uid, now()?.as_millis(), uuid).map_err(|e| {
+            error!("Failed to sign anticode: {}", e);
+            e
+        })
     }
 }
 
@@ -288,9 +384,17 @@ impl Parser for HuyaParser {
 }
 
 #[tauri::command]
-pub async fn parse_huya(room_id: Option<u64>, url: String) -> LsarResult<ParsedResult> {
-    info!("Parsing Huya stream. Room ID: {:?}, URL: {}", room_id, url);
-    let mut huya = HuyaParser::new(room_id, url);
+pub async fn parse_huya(
+    room_id: Option<u64>,
+    url: String,
+    ratio: Option<u32>,
+    cdn: Option<String>,
+) -> LsarResult<ParsedResult> {
+    info!(
+        "Parsing Huya stream. Room ID: {:?}, URL: {}, ratio: {:?}, CDN: {:?}",
+        room_id, url, ratio, cdn
+    );
+    let mut huya = HuyaParser::new(room_id, url).with_options(ratio, cdn);
     let result = huya.parse().await;
     match &result {
         Ok(_) => info!("Successfully parsed Huya stream"),
@@ -298,3 +402,26 @@ pub async fn parse_huya(room_id: Option<u64>, url: String) -> LsarResult<ParsedR
     }
     result
 }
+
+/// 在 `wsTime` 过期前重新签名虎牙直播流链接，以便长时间播放
+#[tauri::command]
+pub async fn refresh_huya_link(room_id: u64, url: String) -> LsarResult<RefreshedLink> {
+    info!("Refreshing Huya link. Room ID: {}, URL: {}", room_id, url);
+    let huya = HuyaParser::new(Some(room_id), String::new());
+    let result = huya.refresh_link(room_id, &url).await;
+    match &result {
+        Ok(_) => info!("Successfully refreshed Huya link"),
+        Err(e) => error!("Failed to refresh Huya link: {}", e),
+    }
+    result
+}
+
+/// 获取虎牙直播流链接的剩余有效期
+#[tauri::command]
+pub async fn get_huya_link_lifetime(url: String) -> LsarResult<LinkLifetime> {
+    let expires_in = AnticodeParser::remaining_lifetime(&url)?.as_secs();
+    Ok(LinkLifetime {
+        expires_in,
+        should_refresh: expires_in <= REFRESH_THRESHOLD_SECS,
+    })
+}
@@ -16,11 +16,32 @@ pub(super) struct CacheProfileData {
 #[serde(rename_all(deserialize = "camelCase"))]
 pub(super) struct StreamInfo {
     pub(super) base_steam_info_list: Vec<BaseSteamInfo>,
+    #[serde(default)]
+    pub(super) flv: Option<MultiStreamInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub(super) struct MultiStreamInfo {
+    /// 可选码率，对应页面中的 `vBitRateInfo`
+    #[serde(default)]
+    pub(super) rate_array: Vec<BitRateInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub(super) struct BitRateInfo {
+    pub(super) s_display_name: String,
+    /// 0 为原画
+    pub(super) i_bit_rate: u32,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all(deserialize = "camelCase"))]
 pub(super) struct BaseSteamInfo {
+    pub(super) s_cdn_type: String,
+    #[serde(rename(deserialize = "iPCPriorityRate"), default)]
+    pub(super) i_pc_priority_rate: i32,
     pub(super) s_stream_name: String,
     pub(super) s_flv_url: String,
     pub(super) s_flv_anti_code: String,
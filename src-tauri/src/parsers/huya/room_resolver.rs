@@ -0,0 +1,99 @@
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError};
+
+use super::BASE_URL;
+
+/// 分享链接中可能携带房间号的查询参数
+const ROOM_ID_PARAMS: [&str; 3] = ["roomid", "roomId", "profileRoom"];
+
+/// 将用户输入的各种虎牙链接转换为可以提取 `profileRoom` 的桌面端页面地址
+///
+/// 支持的形式：
+/// - `huya.com/<房间号>`、`huya.com/lpl`、`huya.com/s` 等靓号或别名
+/// - `m.huya.com/<房间号或别名>` 手机端页面
+/// - 带有 `roomid` 参数的 App 分享链接，如 `hd.huya.com/share_room/...?roomid=<房间号>`
+pub(super) fn resolve_page_url(input: &str) -> LsarResult<String> {
+    let url = Url::parse(input)?;
+    let host = url.host_str().unwrap_or_default();
+
+    if host != "huya.com" && !host.ends_with(".huya.com") {
+        error!("Unsupported Huya URL: {}", url);
+        return Err(LsarError::Other(format!("不支持的虎牙链接: {}", url)));
+    }
+
+    if let Some(room_id) = url
+        .query_pairs()
+        .find(|(k, _)| ROOM_ID_PARAMS.contains(&k.as_ref()))
+        .map(|(_, v)| v.into_owned())
+        .filter(|v| !v.is_empty())
+    {
+        debug!("Found room ID in share link: {}", room_id);
+        return Ok(format!("{}{}", BASE_URL, room_id));
+    }
+
+    let segment = url
+        .path_segments()
+        .and_then(|mut s| s.find(|s| !s.is_empty()))
+        .ok_or(MissKeyFieldError::RoomId)?;
+
+    match host {
+        "www.huya.com" | "huya.com" | "m.huya.com" => Ok(format!("{}{}", BASE_URL, segment)),
+        // 其他子域名（如短链）由请求时的重定向处理
+        _ => Ok(input.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_desktop_url() {
+        assert_eq!(
+            resolve_page_url("https://www.huya.com/11342412").unwrap(),
+            "https://www.huya.com/11342412"
+        );
+        assert_eq!(
+            resolve_page_url("https://huya.com/lpl").unwrap(),
+            "https://www.huya.com/lpl"
+        );
+        assert_eq!(
+            resolve_page_url("https://www.huya.com/s?from=search").unwrap(),
+            "https://www.huya.com/s"
+        );
+    }
+
+    #[test]
+    fn test_resolve_mobile_url() {
+        assert_eq!(
+            resolve_page_url("https://m.huya.com/11342412?shareid=1").unwrap(),
+            "https://www.huya.com/11342412"
+        );
+        assert_eq!(
+            resolve_page_url("https://m.huya.com/lpl").unwrap(),
+            "https://www.huya.com/lpl"
+        );
+    }
+
+    #[test]
+    fn test_resolve_share_link() {
+        assert_eq!(
+            resolve_page_url(
+                "https://hd.huya.com/share_room/index.html?roomid=660000&shareid=8&platform=5"
+            )
+            .unwrap(),
+            "https://www.huya.com/660000"
+        );
+        assert_eq!(
+            resolve_page_url("https://m.huya.com/?shareUid=1&roomId=11342412").unwrap(),
+            "https://www.huya.com/11342412"
+        );
+    }
+
+    #[test]
+    fn test_resolve_unsupported_url() {
+        assert!(resolve_page_url("https://www.douyu.com/11342412").is_err());
+        assert!(resolve_page_url("https://www.huya.com/").is_err());
+    }
+}
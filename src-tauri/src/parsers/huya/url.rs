@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use url::Url;
 
 use crate::error::LsarResult;
@@ -7,12 +5,12 @@ use crate::error::LsarResult;
 pub(super) struct UrlParser;
 
 impl UrlParser {
-    pub(super) fn parse_query(code: &str) -> LsarResult<HashMap<String, Vec<String>>> {
+    /// 按原始顺序解析查询参数，保证生成的链接参数顺序固定
+    pub(super) fn parse_query(code: &str) -> LsarResult<Vec<(String, String)>> {
         trace!("Parsing URL query");
         Ok(Url::parse(&format!("http://example.com?{}", code))?
             .query_pairs()
-            .into_iter()
-            .map(|(k, v)| (k.into_owned(), vec![v.into_owned()]))
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
             .collect())
     }
 }
@@ -0,0 +1,73 @@
+mod room_resolver;
+mod state;
+
+use crate::error::LsarResult;
+use crate::network::http::Client;
+use crate::parsers::{ParsedResult, Parser};
+use crate::platform::Platform;
+
+use self::room_resolver::resolve_principal_id;
+use self::state::{extract_initial_state, parse_live_room};
+
+const LIVE_BASE_URL: &str = "https://live.kuaishou.com/u/";
+
+pub struct KuaishouParser {
+    /// 纯数字的主播 ID 或用户输入的链接
+    input: String,
+    http_client: Client,
+}
+
+impl KuaishouParser {
+    fn new(input: String) -> Self {
+        Self {
+            input,
+            http_client: Client::new(),
+        }
+    }
+
+    async fn page_url(&self) -> LsarResult<String> {
+        // 部分主播 ID 是纯数字，前端会将其作为房间号传入
+        if self.input.parse::<u64>().is_ok() {
+            return Ok(format!("{}{}", LIVE_BASE_URL, self.input));
+        }
+
+        let principal_id = resolve_principal_id(&self.http_client, &self.input).await?;
+        Ok(format!("{}{}", LIVE_BASE_URL, principal_id))
+    }
+}
+
+impl Parser for KuaishouParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let url = self.page_url().await?;
+        let html = self.http_client.get_text(&url).await?;
+
+        let state = extract_initial_state(&html)?;
+        let room = parse_live_room(&state)?;
+        debug!(
+            anchor = room.anchor,
+            links = room.links.len(),
+            "Parsed Kuaishou room"
+        );
+
+        Ok(ParsedResult {
+            platform: Platform::Kuaishou,
+            title: room.title,
+            anchor: room.anchor,
+            room_id: room.principal_id,
+            category: room.category,
+            links: room.links,
+            qualities: room.qualities,
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+}
+
+/// `room_id` 和 `url` 二选一，`room_id` 为纯数字的主播 ID
+#[tauri::command]
+pub async fn parse_kuaishou(room_id: Option<u64>, url: String) -> LsarResult<ParsedResult> {
+    let input = room_id.map_or(url, |id| id.to_string());
+    let mut kuaishou = KuaishouParser::new(input);
+    kuaishou.parse().await
+}
@@ -0,0 +1,97 @@
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError};
+use crate::network::http::Client;
+
+/// 快手用户可能输入的链接形式
+#[derive(Debug, PartialEq, Eq)]
+enum KuaishouUrl {
+    /// `live.kuaishou.com/u/<id>` 或手机端 `/fw/live/<id>`，路径中即为主播 ID
+    Principal(String),
+    /// `v.kuaishou.com/xxxx` 分享短链，需要跟随重定向
+    ShortLink,
+}
+
+impl KuaishouUrl {
+    fn classify(url: &Url) -> LsarResult<Self> {
+        let host = url.host_str().unwrap_or_default();
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|s| s.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+
+        if host == "v.kuaishou.com" {
+            return Ok(KuaishouUrl::ShortLink);
+        }
+
+        // 短链会重定向到 chenzhongtech.com 等手机端域名
+        let supported = ["kuaishou.com", "chenzhongtech.com", "gifshow.com"]
+            .iter()
+            .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+        if !supported {
+            error!("Unsupported Kuaishou URL: {}", url);
+            return Err(LsarError::Other(format!("不支持的快手链接: {}", url)));
+        }
+
+        segments
+            .iter()
+            .position(|s| *s == "u" || *s == "live")
+            .and_then(|i| segments.get(i + 1))
+            .map(|s| KuaishouUrl::Principal(s.to_string()))
+            .ok_or_else(|| MissKeyFieldError::RoomId.into())
+    }
+}
+
+/// 将各种形式的快手链接解析为主播 ID（principalId）
+pub(super) async fn resolve_principal_id(client: &Client, input: &str) -> LsarResult<String> {
+    debug!("Resolving Kuaishou principal ID from: {}", input);
+    let url = Url::parse(input)?;
+
+    let principal_id = match KuaishouUrl::classify(&url)? {
+        KuaishouUrl::Principal(id) => id,
+        KuaishouUrl::ShortLink => {
+            let response = client.get(input).await?;
+            let final_url = response.url().clone();
+            debug!("Short link redirected to: {}", final_url);
+
+            match KuaishouUrl::classify(&final_url)? {
+                KuaishouUrl::Principal(id) => id,
+                KuaishouUrl::ShortLink => {
+                    error!("Short link did not redirect: {}", final_url);
+                    return Err(MissKeyFieldError::RoomId.into());
+                }
+            }
+        }
+    };
+
+    info!("Resolved Kuaishou principal ID: {}", principal_id);
+    Ok(principal_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(input: &str) -> LsarResult<KuaishouUrl> {
+        KuaishouUrl::classify(&Url::parse(input).unwrap())
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify("https://live.kuaishou.com/u/3xwq5mvdqkszmfe").unwrap(),
+            KuaishouUrl::Principal("3xwq5mvdqkszmfe".to_string())
+        );
+        assert_eq!(
+            classify("https://v.kuaishou.com/5xyzAbC").unwrap(),
+            KuaishouUrl::ShortLink
+        );
+        assert_eq!(
+            classify("https://livev.m.chenzhongtech.com/fw/live/KPL704668133?cc=share_copylink")
+                .unwrap(),
+            KuaishouUrl::Principal("KPL704668133".to_string())
+        );
+        assert!(classify("https://live.kuaishou.com/").is_err());
+        assert!(classify("https://www.douyin.com/u/123").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use std::cmp::Reverse;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RequestError, RoomStateError};
+use crate::parsers::{Link, Quality};
+
+/// 编码名与 `playUrls` 中的键对应
+const CODECS: [(&str, &str); 2] = [("h264", "avc"), ("hevc", "hevc")];
+
+/// 从页面的 `__INITIAL_STATE__` 中解析出的直播间信息
+#[derive(Debug)]
+pub(super) struct LiveRoom {
+    pub anchor: String,
+    pub title: String,
+    pub category: String,
+    /// 主播 ID（principalId），即直播间链接中的 ID
+    pub principal_id: String,
+    pub links: Vec<Link>,
+    pub qualities: Vec<Quality>,
+}
+
+/// 提取页面中的初始状态，其中的 `undefined` 不是合法 JSON，替换为 `null`
+pub(super) fn extract_initial_state(html: &str) -> LsarResult<Value> {
+    let re = Regex::new(r"(?s)window\.__INITIAL_STATE__\s*=\s*(\{.*?\});\s*\(function")?;
+    let state = re
+        .captures(html)
+        .and_then(|caps| caps.get(1))
+        .ok_or_else(|| {
+            // 触发风控时页面被替换为验证码页面，不含初始状态
+            if html.contains("captcha") {
+                warn!("Kuaishou page requires captcha verification");
+                LsarError::from(RequestError::Captcha)
+            } else {
+                error!("Failed to find initial state in Kuaishou page");
+                LsarError::from("Failed to find initial state")
+            }
+        })?
+        .as_str();
+
+    let re = Regex::new(r":\s*undefined\b")?;
+    Ok(serde_json::from_str(&re.replace_all(state, ":null"))?)
+}
+
+pub(super) fn parse_live_room(state: &Value) -> LsarResult<LiveRoom> {
+    let play = &state["liveroom"]["playList"][0];
+    if play.is_null() {
+        error!("No play list in Kuaishou initial state");
+        return Err(MissKeyFieldError::RoomId.into());
+    }
+
+    // 未登录或触发风控时 errorType 中有提示，liveStream 为空
+    if let Some(title) = play["errorType"]["title"].as_str() {
+        warn!("Kuaishou returned error: {}", play["errorType"]);
+        return Err(if title.contains("登录") {
+            RequestError::LoginRequired
+        } else {
+            RequestError::Captcha
+        }
+        .into());
+    }
+
+    if !play["isLiving"].as_bool().unwrap_or(false) {
+        info!("Kuaishou room is offline");
+        return Err(RoomStateError::Offline.into());
+    }
+
+    let author = &play["author"];
+    let principal_id = author["id"]
+        .as_str()
+        .ok_or(MissKeyFieldError::RoomId)?
+        .to_string();
+
+    let stream = &play["liveStream"];
+    let (links, qualities) = parse_play_urls(&stream["playUrls"]);
+    if links.is_empty() {
+        info!("Kuaishou room has no stream");
+        return Err(RoomStateError::Offline.into());
+    }
+
+    Ok(LiveRoom {
+        anchor: author["name"]
+            .as_str()
+            .ok_or(MissKeyFieldError::AnchorName)?
+            .to_string(),
+        title: stream["caption"].as_str().unwrap_or_default().to_string(),
+        category: play["gameInfo"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        principal_id,
+        links,
+        qualities,
+    })
+}
+
+/// `playUrls` 新版按编码分组，旧版为数组，每个 `representation` 是一种画质
+fn parse_play_urls(play_urls: &Value) -> (Vec<Link>, Vec<Quality>) {
+    let groups: Vec<(&str, &Value)> = match play_urls {
+        Value::Array(groups) => groups.iter().map(|g| ("avc", g)).collect(),
+        _ => CODECS
+            .iter()
+            .map(|(key, codec)| (*codec, &play_urls[key]))
+            .filter(|(_, g)| !g.is_null())
+            .collect(),
+    };
+
+    let mut links = Vec::new();
+    let mut qualities: Vec<Quality> = Vec::new();
+    for (codec, group) in groups {
+        let representations = group["adaptationSet"]["representation"]
+            .as_array()
+            .into_iter()
+            .flatten();
+
+        for representation in representations {
+            let Some(url) = representation["url"].as_str() else {
+                continue;
+            };
+            let name = representation["name"].as_str().unwrap_or_default();
+            let format = if url.contains(".m3u8") { "HLS" } else { "FLV" };
+            links.push(Link::new(url).quality(name).format(format).codec(codec));
+
+            let level = representation["level"].as_i64().unwrap_or_default();
+            if !qualities.iter().any(|q| q.value == level) {
+                qualities.push(Quality::new(level, name));
+            }
+        }
+    }
+    qualities.sort_unstable_by_key(|q| Reverse(q.value));
+
+    (links, qualities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(html: &str) -> Value {
+        extract_initial_state(html).unwrap()
+    }
+
+    #[test]
+    fn test_parse_live_room() {
+        let room = parse_live_room(&state(include_str!("fixtures/live.html"))).unwrap();
+
+        assert_eq!(room.anchor, "快手主播");
+        assert_eq!(room.title, "今晚不下播");
+        assert_eq!(room.category, "王者荣耀");
+        assert_eq!(room.principal_id, "3xwq5mvdqkszmfe");
+        assert_eq!(room.links.len(), 3);
+        assert_eq!(room.links[0].quality.as_deref(), Some("高清"));
+        assert_eq!(room.links[0].format.as_deref(), Some("FLV"));
+        assert_eq!(room.links[2].codec.as_deref(), Some("hevc"));
+
+        let levels: Vec<i64> = room.qualities.iter().map(|q| q.value).collect();
+        assert_eq!(levels, vec![4, 2]);
+    }
+
+    #[test]
+    fn test_parse_offline_and_error_states() {
+        let offline = state(include_str!("fixtures/offline.html"));
+        assert!(matches!(
+            parse_live_room(&offline),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+
+        let login = serde_json::json!({
+            "liveroom": {"playList": [{"errorType": {"title": "请登录后观看", "type": 2}}]}
+        });
+        assert!(matches!(
+            parse_live_room(&login),
+            Err(LsarError::Request(RequestError::LoginRequired))
+        ));
+
+        assert!(matches!(
+            extract_initial_state("<html><script src=\"/captcha.js\"></script></html>"),
+            Err(LsarError::Request(RequestError::Captcha))
+        ));
+    }
+}
@@ -1,17 +1,41 @@
+mod acfun;
 mod bigo;
 mod bilibili;
+mod cc;
+mod chzzk;
 mod douyin;
 mod douyu;
+mod hls;
 mod huya;
+mod kuaishou;
+mod niconico;
+mod soop;
+mod tiktok;
+mod twitcasting;
+mod twitch;
+mod youtube;
 mod yy;
 
 use serde::Serialize;
 
+pub use self::acfun::parse_acfun;
 pub use self::bigo::parse_bigo;
-pub use self::bilibili::parse_bilibili;
+pub use self::bilibili::{
+    generate_bilibili_qrcode, get_bilibili_followed_live_rooms, parse_bilibili,
+    poll_bilibili_qrcode,
+};
+pub use self::cc::parse_cc;
+pub use self::chzzk::parse_chzzk;
 pub use self::douyin::parse_douyin;
 pub use self::douyu::parse_douyu;
-pub use self::huya::parse_huya;
+pub use self::huya::{get_huya_link_lifetime, parse_huya, refresh_huya_link};
+pub use self::kuaishou::parse_kuaishou;
+pub use self::niconico::{parse_niconico, stop_niconico_keep_alive};
+pub use self::soop::parse_soop;
+pub use self::tiktok::parse_tiktok;
+pub use self::twitcasting::parse_twitcasting;
+pub use self::twitch::parse_twitch;
+pub use self::youtube::parse_youtube;
 pub use self::yy::parse_yy;
 
 use crate::{error::LsarResult, platform::Platform};
@@ -21,10 +45,90 @@ pub struct ParsedResult {
     platform: Platform,
     title: String,
     anchor: String,
+    /// 各平台的房间号或频道名，部分平台（如 Twitch、Bigo 的用户名）不是数字
     #[serde(rename(serialize = "roomID"))]
-    room_id: u64,
+    room_id: String,
     category: String,
-    links: Vec<String>,
+    links: Vec<Link>,
+    /// 可选的画质，不支持选择画质的平台为空
+    qualities: Vec<Quality>,
+    /// 以游客身份解析，更高的画质需要登录
+    guest: bool,
+    /// 预定直播尚未开始时的开始时间（Unix 时间戳，秒），此时没有直播流链接
+    #[serde(rename = "scheduledStart", skip_serializing_if = "Option::is_none")]
+    scheduled_start: Option<i64>,
+    /// 后端保活的观看会话 ID，结束会话时需要传回
+    #[serde(rename = "keepAliveSession", skip_serializing_if = "Option::is_none")]
+    keep_alive_session: Option<u64>,
+}
+
+/// 直播流链接，附带用于区分各链接的说明
+#[derive(Debug, Default, Serialize)]
+pub struct Link {
+    url: String,
+    /// 画质，如“原画”“蓝光 10M”
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quality: Option<String>,
+    /// 线路或 CDN
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cdn: Option<String>,
+    /// 协议或封装格式，如 FLV、HLS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    /// 视频编码，如 AVC、HEVC
+    #[serde(skip_serializing_if = "Option::is_none")]
+    codec: Option<String>,
+}
+
+impl Link {
+    fn new<S: Into<String>>(url: S) -> Self {
+        Link {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    fn quality<S: Into<String>>(mut self, quality: S) -> Self {
+        self.quality = Some(quality.into());
+        self
+    }
+
+    fn cdn<S: Into<String>>(mut self, cdn: S) -> Self {
+        self.cdn = Some(cdn.into());
+        self
+    }
+
+    fn format<S: Into<String>>(mut self, format: S) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+
+    fn codec<S: Into<String>>(mut self, codec: S) -> Self {
+        self.codec = Some(codec.into());
+        self
+    }
+}
+
+impl From<String> for Link {
+    fn from(url: String) -> Self {
+        Link::new(url)
+    }
+}
+
+/// 平台提供的画质选项，`value` 为请求该画质时传给解析命令的参数
+#[derive(Debug, Serialize)]
+pub struct Quality {
+    value: i64,
+    name: String,
+}
+
+impl Quality {
+    fn new<S: Into<String>>(value: i64, name: S) -> Self {
+        Quality {
+            value,
+            name: name.into(),
+        }
+    }
 }
 
 trait Parser {
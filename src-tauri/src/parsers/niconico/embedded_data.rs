@@ -0,0 +1,173 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
+
+/// 从观看页面 `embedded-data` 中解析出的节目信息
+#[derive(Debug, PartialEq)]
+pub(super) struct Program {
+    pub title: String,
+    pub anchor: String,
+    /// 节目所属的频道、社区或用户，可用于打开其当前的节目
+    pub provider_id: Option<String>,
+    pub state: ProgramState,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) enum ProgramState {
+    /// 直播中，需要通过观看 WebSocket 获取直播流
+    OnAir { web_socket_url: String },
+    /// 预约的节目尚未开始，值为开始时间（Unix 时间戳，秒）
+    Released { begin_time: i64 },
+}
+
+/// 提取 `<script id="embedded-data" data-props="...">` 中经过 HTML 转义的 JSON
+pub(super) fn extract_embedded_data(html: &str) -> LsarResult<Value> {
+    let re = Regex::new(r#"<script id="embedded-data" data-props="([^"]*)""#)?;
+    let props = re
+        .captures(html)
+        .and_then(|caps| caps.get(1))
+        .ok_or_else(|| {
+            error!("Failed to find embedded-data in Niconico page");
+            LsarError::from("Failed to find embedded-data")
+        })?
+        .as_str();
+
+    Ok(serde_json::from_str(&unescape_html(props))?)
+}
+
+pub(super) fn parse_program(data: &Value) -> LsarResult<Program> {
+    let program = &data["program"];
+    if program.is_null() {
+        error!("No program in Niconico embedded-data");
+        return Err(RoomStateError::NotExists.into());
+    }
+
+    let state = match program["status"].as_str().unwrap_or_default() {
+        "ON_AIR" => {
+            // 会员限定等当前账号无权观看的节目没有观看地址
+            let web_socket_url = data["site"]["relive"]["webSocketUrl"]
+                .as_str()
+                .filter(|url| !url.is_empty())
+                .ok_or_else(|| {
+                    warn!("Niconico program has no watch WebSocket URL");
+                    RoomStateError::Restricted
+                })?;
+            ProgramState::OnAir {
+                web_socket_url: web_socket_url.to_string(),
+            }
+        }
+        "RELEASED" => ProgramState::Released {
+            begin_time: program["beginTime"].as_i64().unwrap_or_default(),
+        },
+        status => {
+            info!("Niconico program is not on air, status: {}", status);
+            return Err(RoomStateError::Offline.into());
+        }
+    };
+
+    Ok(Program {
+        title: program["title"]
+            .as_str()
+            .ok_or(MissKeyFieldError::Title)?
+            .to_string(),
+        anchor: program["supplier"]["name"]
+            .as_str()
+            .ok_or(MissKeyFieldError::AnchorName)?
+            .to_string(),
+        provider_id: provider_id(data),
+        state,
+    })
+}
+
+/// 节目 ID（lv）每次直播都会变化，需要保存频道（ch）、社区（co）或用户 ID，
+/// 用户 ID 以 `user/<ID>` 的形式保存，与观看页面的路径一致
+fn provider_id(data: &Value) -> Option<String> {
+    let social_group = data["socialGroup"]["id"].as_str().unwrap_or_default();
+    if social_group.starts_with("ch") || social_group.starts_with("co") {
+        return Some(social_group.to_string());
+    }
+
+    let provider = &data["program"]["supplier"]["programProviderId"];
+    provider
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| provider.as_u64().map(|id| id.to_string()))
+        .filter(|id| !id.is_empty())
+        .map(|id| format!("user/{}", id))
+}
+
+/// `&amp;` 必须最后替换，以免把 `&amp;quot;` 还原成引号
+fn unescape_html(input: &str) -> String {
+    input
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_extract_embedded_data() {
+        let html = r#"<script id="embedded-data" data-props="{&quot;program&quot;:{&quot;title&quot;:&quot;a &amp;quot;b&amp;quot;&quot;}}"></script>"#;
+        let data = extract_embedded_data(html).unwrap();
+        assert_eq!(data["program"]["title"], "a &quot;b&quot;");
+
+        assert!(extract_embedded_data("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_parse_program() {
+        let data = |status: &str, url: &str| {
+            json!({
+                "program": {
+                    "title": "テスト",
+                    "status": status,
+                    "beginTime": 1735689600,
+                    "supplier": {"name": "tester", "programProviderId": "12345678"}
+                },
+                "site": {"relive": {"webSocketUrl": url}}
+            })
+        };
+
+        let program = parse_program(&data("ON_AIR", "wss://a.live2.nicovideo.jp/watch")).unwrap();
+        assert_eq!(program.anchor, "tester");
+        assert_eq!(program.provider_id.as_deref(), Some("user/12345678"));
+        assert_eq!(
+            program.state,
+            ProgramState::OnAir {
+                web_socket_url: "wss://a.live2.nicovideo.jp/watch".to_string()
+            }
+        );
+
+        let program = parse_program(&data("RELEASED", "")).unwrap();
+        assert_eq!(
+            program.state,
+            ProgramState::Released {
+                begin_time: 1735689600
+            }
+        );
+
+        let mut channel = data("RELEASED", "");
+        channel["socialGroup"] = json!({"id": "ch2646436"});
+        assert_eq!(
+            parse_program(&channel).unwrap().provider_id.as_deref(),
+            Some("ch2646436")
+        );
+
+        assert!(matches!(
+            parse_program(&data("ON_AIR", "")),
+            Err(LsarError::RoomState(RoomStateError::Restricted))
+        ));
+        assert!(matches!(
+            parse_program(&data("ENDED", "")),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+    }
+}
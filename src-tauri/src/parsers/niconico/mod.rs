@@ -0,0 +1,151 @@
+mod embedded_data;
+mod watch;
+
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError};
+use crate::network::http::Client;
+use crate::parsers::hls::{into_links, parse_master_playlist};
+use crate::parsers::{Link, ParsedResult, Parser};
+use crate::platform::Platform;
+
+use self::embedded_data::{extract_embedded_data, parse_program, ProgramState};
+use self::watch::{spawn_keep_alive, stop_keep_alive, WatchSession};
+
+const WATCH_BASE_URL: &str = "https://live.nicovideo.jp/watch/";
+
+pub struct NiconicoParser {
+    /// 节目 ID（lv）、社区或频道 ID（co、ch）或 `user/<用户 ID>`，后三者对应其当前的节目
+    id: String,
+    http_client: Client,
+}
+
+impl NiconicoParser {
+    fn new(id: String) -> Self {
+        Self {
+            id,
+            http_client: Client::new(),
+        }
+    }
+}
+
+impl Parser for NiconicoParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let html = self
+            .http_client
+            .get_text(&format!("{}{}", WATCH_BASE_URL, self.id))
+            .await?;
+        let program = parse_program(&extract_embedded_data(&html)?)?;
+
+        let mut result = ParsedResult {
+            platform: Platform::Niconico,
+            title: program.title,
+            anchor: program.anchor,
+            room_id: program.provider_id.unwrap_or_else(|| self.id.clone()),
+            category: String::new(),
+            links: Vec::new(),
+            qualities: Vec::new(),
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        };
+
+        match program.state {
+            ProgramState::Released { begin_time } => {
+                info!("Niconico program is scheduled at {}", begin_time);
+                result.scheduled_start = Some(begin_time);
+            }
+            ProgramState::OnAir { web_socket_url } => {
+                let mut session = WatchSession::connect(&web_socket_url).await?;
+                let uri = session.start_watching().await?;
+
+                let url = Url::parse(&uri)?;
+                let playlist = self.http_client.get_text(url.as_str()).await?;
+                (result.links, result.qualities) =
+                    into_links(parse_master_playlist(&url, &playlist)?);
+                if result.links.is_empty() {
+                    result.links.push(Link::new(uri).format("HLS"));
+                }
+
+                // 直播流链接只在观看会话保持期间有效
+                result.keep_alive_session = Some(spawn_keep_alive(session));
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// 从 `live.nicovideo.jp/watch/<ID>` 或 `nico.ms/<ID>` 中取出节目、社区或频道 ID，
+/// `live.nicovideo.jp/watch/user/<用户 ID>` 则取出 `user/<用户 ID>`
+fn resolve_id(input: &str) -> LsarResult<String> {
+    let url = Url::parse(input)?;
+    let host = url.host_str().unwrap_or_default();
+    if host != "nico.ms" && host != "nicovideo.jp" && !host.ends_with(".nicovideo.jp") {
+        error!("Unsupported Niconico URL: {}", url);
+        return Err(LsarError::Other(format!("不支持的 Niconico 链接: {}", url)));
+    }
+
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let segments: Vec<_> = url.path_segments().into_iter().flatten().collect();
+    if let Some(pair) = segments
+        .windows(2)
+        .find(|pair| pair[0] == "user" && is_digits(pair[1]))
+    {
+        return Ok(format!("user/{}", pair[1]));
+    }
+
+    segments
+        .into_iter()
+        .find(|s| {
+            ["lv", "co", "ch"]
+                .iter()
+                .any(|prefix| s.strip_prefix(prefix).is_some_and(is_digits))
+        })
+        .map(str::to_string)
+        .ok_or_else(|| MissKeyFieldError::RoomId.into())
+}
+
+#[tauri::command]
+pub async fn parse_niconico(url: String) -> LsarResult<ParsedResult> {
+    let id = resolve_id(&url)?;
+    info!("Parsing Niconico live: {}", id);
+
+    let mut niconico = NiconicoParser::new(id);
+    niconico.parse().await
+}
+
+/// 不再播放时终止观看会话
+///
+/// `session` 为解析结果中的 `keepAliveSession`，已被之后的解析替换的会话会被忽略
+#[tauri::command]
+pub fn stop_niconico_keep_alive(session: u64) {
+    stop_keep_alive(session);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_id() {
+        assert_eq!(
+            resolve_id("https://live.nicovideo.jp/watch/lv345678901?ref=top").unwrap(),
+            "lv345678901"
+        );
+        assert_eq!(
+            resolve_id("https://live.nicovideo.jp/watch/co1234567").unwrap(),
+            "co1234567"
+        );
+        assert_eq!(
+            resolve_id("https://nico.ms/lv345678901").unwrap(),
+            "lv345678901"
+        );
+        assert_eq!(
+            resolve_id("https://live.nicovideo.jp/watch/user/12345678").unwrap(),
+            "user/12345678"
+        );
+        assert!(resolve_id("https://live.nicovideo.jp/watch/lv").is_err());
+        assert!(resolve_id("https://www.youtube.com/watch/lv345678901").is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::async_runtime::{self, JoinHandle};
+use tokio::net::TcpStream;
+use tokio::time::{interval, timeout, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::error::{LsarError, LsarResult, RoomStateError};
+
+/// 等待 `stream` 消息的超时时间
+const STREAM_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 正在保活的观看会话，同一时间只保留一个
+static KEEP_ALIVE: Mutex<Option<(u64, JoinHandle<()>)>> = Mutex::new(None);
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 观看 WebSocket 中服务端发送的消息，只列出需要处理的类型
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub(super) enum ServerMessage {
+    /// 需要立即回复 `pong`
+    Ping,
+    /// 座位已分配，需要每隔 `keepIntervalSec` 秒发送 `keepSeat`
+    #[serde(rename_all = "camelCase")]
+    Seat {
+        keep_interval_sec: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Stream {
+        uri: String,
+        #[serde(default)]
+        available_qualities: Vec<String>,
+    },
+    Disconnect {
+        reason: String,
+    },
+    Error {
+        code: String,
+    },
+    /// `statistics`、`room` 等不需要处理的消息
+    #[serde(skip)]
+    Other,
+}
+
+impl ServerMessage {
+    /// 其他类型的消息各自带有不同的 `data`，不逐一定义
+    fn parse(text: &str) -> LsarResult<Self> {
+        let value: Value = serde_json::from_str(text)?;
+        match value["type"].as_str() {
+            Some("ping" | "seat" | "stream" | "disconnect" | "error") => {
+                Ok(serde_json::from_value(value)?)
+            }
+            _ => Ok(ServerMessage::Other),
+        }
+    }
+
+    fn into_error(self) -> Option<LsarError> {
+        match self {
+            ServerMessage::Error { code } => Some(match code.as_str() {
+                "NOT_ON_AIR" => RoomStateError::Offline.into(),
+                "BROADCAST_NOT_FOUND" => RoomStateError::NotExists.into(),
+                "NO_PERMISSION" => RoomStateError::Restricted.into(),
+                _ => LsarError::Other(format!("Niconico 观看会话错误：{}", code)),
+            }),
+            ServerMessage::Disconnect { reason } => Some(LsarError::Other(format!(
+                "Niconico 观看会话已断开：{}",
+                reason
+            ))),
+            _ => None,
+        }
+    }
+}
+
+/// 观看 WebSocket 会话，连接断开后直播流链接随之失效
+pub(super) struct WatchSession {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    keep_interval: Option<Duration>,
+}
+
+impl WatchSession {
+    pub async fn connect(url: &str) -> LsarResult<Self> {
+        debug!("Connecting to Niconico watch WebSocket");
+        let (socket, _) = connect_async(url).await.map_err(|e| {
+            error!("Failed to connect to Niconico watch WebSocket: {}", e);
+            LsarError::Other(e.to_string())
+        })?;
+
+        Ok(Self {
+            socket,
+            keep_interval: None,
+        })
+    }
+
+    async fn send(&mut self, message: serde_json::Value) -> LsarResult<()> {
+        trace!("Sending Niconico watch message: {}", message);
+        self.socket
+            .send(Message::text(message.to_string()))
+            .await
+            .map_err(|e| LsarError::Other(e.to_string()))
+    }
+
+    /// 接收下一条服务端消息，`ping` 和 `seat` 在此处理，连接关闭时返回 `None`
+    async fn recv(&mut self) -> LsarResult<Option<ServerMessage>> {
+        while let Some(message) = self.socket.next().await {
+            let text = match message.map_err(|e| LsarError::Other(e.to_string()))? {
+                Message::Text(text) => text,
+                Message::Close(frame) => {
+                    debug!("Niconico watch WebSocket closed: {:?}", frame);
+                    return Ok(None);
+                }
+                _ => continue,
+            };
+            trace!("Received Niconico watch message: {}", text.as_str());
+
+            let message = ServerMessage::parse(text.as_str())?;
+            match message {
+                ServerMessage::Ping => self.send(json!({"type": "pong"})).await?,
+                ServerMessage::Seat { keep_interval_sec } => {
+                    self.keep_interval = Some(Duration::from_secs(keep_interval_sec.max(1)));
+                }
+                message => return Ok(Some(message)),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// 发送 `startWatching` 并等待 `stream` 消息，返回 HLS 主播放列表地址
+    pub async fn start_watching(&mut self) -> LsarResult<String> {
+        self.send(json!({
+            "type": "startWatching",
+            "data": {
+                "stream": {
+                    "quality": "abr",
+                    "protocol": "hls",
+                    "latency": "low",
+                    "chasePlay": false
+                },
+                "room": {"protocol": "webSocket", "commentable": true},
+                "reconnect": false
+            }
+        }))
+        .await?;
+
+        let wait = async {
+            loop {
+                match self.recv().await? {
+                    Some(ServerMessage::Stream {
+                        uri,
+                        available_qualities,
+                    }) => {
+                        debug!("Niconico available qualities: {:?}", available_qualities);
+                        return Ok(uri);
+                    }
+                    Some(message) => {
+                        if let Some(e) = message.into_error() {
+                            return Err(e);
+                        }
+                    }
+                    None => return Err(LsarError::from("Niconico 观看会话意外断开")),
+                }
+            }
+        };
+
+        timeout(STREAM_TIMEOUT, wait).await.map_err(|_| {
+            error!("Timed out waiting for Niconico stream message");
+            LsarError::from("等待 Niconico 直播流超时")
+        })?
+    }
+
+    /// 持续回复 `ping` 并定期发送 `keepSeat`，直到连接断开或被 `stop_keep_alive` 终止
+    async fn keep_alive(mut self) {
+        let mut ticker = interval(self.keep_interval.unwrap_or(Duration::from_secs(30)));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // 第一次 tick 立即完成，跳过
+        ticker.tick().await;
+
+        loop {
+            let message = tokio::select! {
+                _ = ticker.tick() => None,
+                message = self.recv() => Some(message),
+            };
+
+            let result = match message {
+                None => self.send(json!({"type": "keepSeat"})).await,
+                Some(Ok(Some(message))) => message.into_error().map_or(Ok(()), Err),
+                Some(Ok(None)) => break,
+                Some(Err(e)) => Err(e),
+            };
+
+            if let Err(e) = result {
+                warn!("Niconico keep-alive stopped: {}", e);
+                break;
+            }
+        }
+
+        info!("Niconico watch session ended");
+    }
+}
+
+/// 在后台保活观看会话并返回会话 ID，之前的会话会被终止
+pub(super) fn spawn_keep_alive(session: WatchSession) -> u64 {
+    let id = NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+    let handle = async_runtime::spawn(session.keep_alive());
+    if let Some((previous_id, previous)) = KEEP_ALIVE.lock().unwrap().replace((id, handle)) {
+        debug!("Stopping previous Niconico watch session {}", previous_id);
+        previous.abort();
+    }
+    id
+}
+
+/// 只终止指定的会话，已被新会话替换时不做处理
+pub(super) fn stop_keep_alive(id: u64) {
+    let mut keep_alive = KEEP_ALIVE.lock().unwrap();
+    if keep_alive
+        .as_ref()
+        .is_some_and(|(current, _)| *current == id)
+    {
+        let (_, handle) = keep_alive.take().unwrap();
+        info!("Stopping Niconico watch session {}", id);
+        handle.abort();
+    } else {
+        debug!("Niconico watch session {} is no longer active", id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_message() {
+        let parse = |s: &str| ServerMessage::parse(s).unwrap();
+
+        assert_eq!(parse(r#"{"type":"ping"}"#), ServerMessage::Ping);
+        assert_eq!(
+            parse(r#"{"type":"seat","data":{"keepIntervalSec":30}}"#),
+            ServerMessage::Seat {
+                keep_interval_sec: 30
+            }
+        );
+        assert_eq!(
+            parse(
+                r#"{"type":"stream","data":{"uri":"https://liveedge.dmc.nico/master.m3u8","quality":"abr","availableQualities":["abr","high"],"protocol":"hls"}}"#
+            ),
+            ServerMessage::Stream {
+                uri: "https://liveedge.dmc.nico/master.m3u8".to_string(),
+                available_qualities: vec!["abr".to_string(), "high".to_string()],
+            }
+        );
+        assert_eq!(
+            parse(r#"{"type":"statistics","data":{"viewers":1}}"#),
+            ServerMessage::Other
+        );
+
+        assert!(matches!(
+            parse(r#"{"type":"error","data":{"code":"NOT_ON_AIR"}}"#).into_error(),
+            Some(LsarError::RoomState(RoomStateError::Offline))
+        ));
+        assert!(parse(r#"{"type":"ping"}"#).into_error().is_none());
+    }
+}
@@ -0,0 +1,181 @@
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RequestError, RoomStateError};
+
+/// `player_live_api` 返回的 `RESULT`
+const RESULT_OK: i64 = 1;
+const RESULT_OFFLINE: i64 = 0;
+/// 19 禁直播，需要登录并通过成人认证
+const RESULT_LOGIN_REQUIRED: i64 = -6;
+/// 加密直播的密码错误或未传密码
+const RESULT_WRONG_PASSWORD: i64 = -8;
+
+/// 画质名称和对应的值，从低到高，值用于前端选择画质
+pub(super) const QUALITIES: [(&str, i64, &str); 4] = [
+    ("sd", 1, "标清"),
+    ("hd", 2, "高清"),
+    ("hd4k", 3, "超清"),
+    ("original", 4, "原画"),
+];
+
+/// 正在直播的频道信息
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Channel {
+    pub bj_id: String,
+    pub nickname: String,
+    pub bno: String,
+    pub title: String,
+    pub category: String,
+    pub cdn: String,
+    /// 分配直播流地址的服务器
+    pub rmd: String,
+    /// 频道支持的画质，按 `QUALITIES` 从高到低排列
+    pub presets: Vec<&'static str>,
+}
+
+fn check_result(channel: &Value, has_password: bool) -> LsarResult<()> {
+    match channel["RESULT"].as_i64() {
+        Some(RESULT_OK) => Ok(()),
+        Some(RESULT_OFFLINE) => {
+            info!("SOOP channel is offline");
+            Err(RoomStateError::Offline.into())
+        }
+        Some(RESULT_LOGIN_REQUIRED) => {
+            warn!("SOOP channel requires login");
+            Err(RequestError::LoginRequired.into())
+        }
+        Some(RESULT_WRONG_PASSWORD) if has_password => {
+            info!("SOOP broadcast password is wrong");
+            Err(RoomStateError::WrongPassword.into())
+        }
+        Some(RESULT_WRONG_PASSWORD) => {
+            info!("SOOP broadcast is password protected");
+            Err(RoomStateError::PasswordRequired.into())
+        }
+        _ => {
+            error!("SOOP player_live_api failed: {}", channel);
+            Err(LsarError::from("SOOP 获取频道信息失败"))
+        }
+    }
+}
+
+pub(super) fn parse_channel(resp: &Value, has_password: bool) -> LsarResult<Channel> {
+    let channel = &resp["CHANNEL"];
+    check_result(channel, has_password)?;
+
+    // 加密直播不传密码时 RESULT 同样为 1，只能通过 BPWD 判断
+    if channel["BPWD"].as_str() == Some("Y") && !has_password {
+        info!("SOOP broadcast is password protected");
+        return Err(RoomStateError::PasswordRequired.into());
+    }
+
+    let string = |key: &str| channel[key].as_str().unwrap_or_default().to_string();
+    let presets: Vec<&str> = channel["VIEWPRESET"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|preset| preset["name"].as_str())
+        .collect();
+
+    Ok(Channel {
+        bj_id: channel["BJID"]
+            .as_str()
+            .ok_or(MissKeyFieldError::RoomId)?
+            .to_string(),
+        nickname: channel["BJNICK"]
+            .as_str()
+            .ok_or(MissKeyFieldError::AnchorName)?
+            .to_string(),
+        bno: channel["BNO"]
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| channel["BNO"].as_u64().map(|n| n.to_string()))
+            .ok_or(MissKeyFieldError::RoomId)?,
+        title: string("TITLE"),
+        category: string("CATE_NAME"),
+        cdn: string("CDN"),
+        rmd: string("RMD"),
+        presets: QUALITIES
+            .iter()
+            .rev()
+            .map(|(name, _, _)| *name)
+            .filter(|name| presets.contains(name))
+            .collect(),
+    })
+}
+
+/// 获取播放凭证 AID
+pub(super) fn parse_aid(resp: &Value, has_password: bool) -> LsarResult<String> {
+    let channel = &resp["CHANNEL"];
+    check_result(channel, has_password)?;
+
+    channel["AID"].as_str().map(str::to_string).ok_or_else(|| {
+        error!("No AID in SOOP response: {}", resp);
+        LsarError::from("SOOP 获取播放凭证失败")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn channel(result: i64, bpwd: &str) -> Value {
+        json!({
+            "CHANNEL": {
+                "RESULT": result,
+                "BJID": "phonics1",
+                "BJNICK": "BJ",
+                "BNO": "281234567",
+                "TITLE": "title",
+                "CATE_NAME": "토크/캠방",
+                "CDN": "gs_cdn_pc_web",
+                "RMD": "https://livestream-manager.sooplive.co.kr",
+                "BPWD": bpwd,
+                "VIEWPRESET": [{"name": "auto"}, {"name": "sd"}, {"name": "hd"}, {"name": "original"}]
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_channel() {
+        let parsed = parse_channel(&channel(1, "N"), false).unwrap();
+        assert_eq!(parsed.bno, "281234567");
+        assert_eq!(parsed.presets, vec!["original", "hd", "sd"]);
+
+        assert!(parse_channel(&channel(1, "Y"), true).is_ok());
+        assert!(matches!(
+            parse_channel(&channel(1, "Y"), false),
+            Err(LsarError::RoomState(RoomStateError::PasswordRequired))
+        ));
+        assert!(matches!(
+            parse_channel(&channel(0, "N"), false),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+        assert!(matches!(
+            parse_channel(&channel(-6, "N"), false),
+            Err(LsarError::Request(RequestError::LoginRequired))
+        ));
+    }
+
+    #[test]
+    fn test_parse_aid() {
+        let resp = json!({"CHANNEL": {"RESULT": 1, "AID": "aid-token"}});
+        assert_eq!(parse_aid(&resp, false).unwrap(), "aid-token");
+
+        let resp = json!({"CHANNEL": {"RESULT": -8}});
+        assert!(matches!(
+            parse_aid(&resp, true),
+            Err(LsarError::RoomState(RoomStateError::WrongPassword))
+        ));
+        assert!(matches!(
+            parse_aid(&resp, false),
+            Err(LsarError::RoomState(RoomStateError::PasswordRequired))
+        ));
+
+        // 其他错误不能当作密码错误
+        let resp = json!({"CHANNEL": {"RESULT": -3}});
+        assert!(matches!(parse_aid(&resp, true), Err(LsarError::Other(_))));
+    }
+}
@@ -0,0 +1,219 @@
+mod channel;
+
+use serde_json::Value;
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
+use crate::network::http::Client;
+use crate::parsers::{Link, ParsedResult, Parser, Quality};
+use crate::platform::Platform;
+
+use self::channel::{parse_aid, parse_channel, Channel, QUALITIES};
+
+const PLAYER_LIVE_API: &str = "https://live.sooplive.co.kr/afreeca/player_live_api.php";
+
+pub struct SoopParser {
+    bj_id: String,
+    /// 直播编号，为空时接口返回当前的直播
+    bno: String,
+    /// 画质值，见 `QUALITIES`，未指定时使用最高画质
+    quality: Option<i64>,
+    /// 加密直播的密码
+    password: String,
+    http_client: Client,
+}
+
+impl SoopParser {
+    fn new(bj_id: String, bno: String) -> Self {
+        Self {
+            bj_id,
+            bno,
+            quality: None,
+            password: String::new(),
+            http_client: Client::new(),
+        }
+    }
+
+    fn with_quality(mut self, quality: Option<i64>) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    fn with_password(mut self, password: Option<String>) -> Self {
+        self.password = password.unwrap_or_default();
+        self
+    }
+
+    async fn player_live_api(
+        &self,
+        bno: &str,
+        request_type: &str,
+        quality: &str,
+    ) -> LsarResult<Value> {
+        let url = format!("{}?bjid={}", PLAYER_LIVE_API, self.bj_id);
+        let body = [
+            ("bid", self.bj_id.as_str()),
+            ("bno", bno),
+            ("type", request_type),
+            ("pwd", self.password.as_str()),
+            ("player_type", "html5"),
+            ("stream_type", "common"),
+            ("quality", quality),
+            ("mode", "landing"),
+            ("from_api", "0"),
+            ("is_revive", "false"),
+        ];
+
+        let resp: Value = self.http_client.post_form(&url, &body, None).await?;
+        trace!("SOOP player_live_api ({}) response: {}", request_type, resp);
+
+        Ok(resp)
+    }
+
+    /// 向分配服务器请求 HLS 播放列表地址
+    async fn get_view_url(&self, channel: &Channel, quality: &str) -> LsarResult<String> {
+        let mut url = Url::parse(&channel.rmd)?.join("/broad_stream_assign.html")?;
+        url.query_pairs_mut()
+            .append_pair("return_type", &channel.cdn)
+            .append_pair(
+                "broad_key",
+                &format!("{}-common-{}-hls", channel.bno, quality),
+            );
+
+        let resp: Value = self.http_client.get_json(url.as_str(), None).await?;
+        resp["view_url"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                error!("No view_url in SOOP response: {}", resp);
+                LsarError::from("SOOP 获取直播流地址失败")
+            })
+    }
+
+    fn select_quality(&self, channel: &Channel) -> LsarResult<&'static str> {
+        let requested = self
+            .quality
+            .and_then(|value| QUALITIES.iter().find(|(_, v, _)| *v == value))
+            .map(|(name, _, _)| *name);
+
+        match requested {
+            Some(name) if channel.presets.contains(&name) => Ok(name),
+            _ => channel
+                .presets
+                .first()
+                .copied()
+                .ok_or_else(|| RoomStateError::Offline.into()),
+        }
+    }
+}
+
+impl Parser for SoopParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let has_password = !self.password.is_empty();
+        let resp = self.player_live_api(&self.bno, "live", "HD").await?;
+        let channel = parse_channel(&resp, has_password)?;
+
+        let quality = self.select_quality(&channel)?;
+        debug!(bno = channel.bno, quality, "Requesting SOOP stream");
+
+        let resp = self.player_live_api(&channel.bno, "aid", quality).await?;
+        let aid = parse_aid(&resp, has_password)?;
+        let view_url = self.get_view_url(&channel, quality).await?;
+
+        let mut url = Url::parse(&view_url)?;
+        url.query_pairs_mut().append_pair("aid", &aid);
+
+        let name_of = |preset: &str| {
+            QUALITIES
+                .iter()
+                .find(|(name, _, _)| *name == preset)
+                .map(|(_, value, label)| (*value, *label))
+        };
+        let qualities = channel
+            .presets
+            .iter()
+            .filter_map(|preset| name_of(preset))
+            .map(|(value, label)| Quality::new(value, label))
+            .collect();
+        let label = name_of(quality).map_or(quality, |(_, label)| label);
+
+        Ok(ParsedResult {
+            platform: Platform::Soop,
+            title: channel.title,
+            anchor: channel.nickname,
+            room_id: channel.bj_id,
+            category: channel.category,
+            links: vec![Link::new(url.to_string()).quality(label).format("HLS")],
+            qualities,
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+}
+
+/// 从 `play.sooplive.co.kr/<bj>/<bno>` 或旧的 `play.afreecatv.com` 链接中取出 BJ ID 和直播编号
+fn resolve_channel(input: &str) -> LsarResult<(String, String)> {
+    let url = Url::parse(input)?;
+    let host = url.host_str().unwrap_or_default();
+
+    let supported = ["sooplive.co.kr", "afreecatv.com"]
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+    if !supported {
+        error!("Unsupported SOOP URL: {}", url);
+        return Err(LsarError::Other(format!("不支持的 SOOP 链接: {}", url)));
+    }
+
+    let mut segments = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty());
+    let bj_id = segments.next().ok_or(MissKeyFieldError::RoomId)?;
+    let bno = segments
+        .next()
+        .filter(|s| s.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or_default();
+
+    Ok((bj_id.to_string(), bno.to_string()))
+}
+
+/// `quality` 为 `qualities` 中的值，`password` 用于加密直播
+#[tauri::command]
+pub async fn parse_soop(
+    url: String,
+    quality: Option<i64>,
+    password: Option<String>,
+) -> LsarResult<ParsedResult> {
+    let (bj_id, bno) = resolve_channel(&url)?;
+    info!("Parsing SOOP channel: {}, bno: {}", bj_id, bno);
+
+    let mut soop = SoopParser::new(bj_id, bno)
+        .with_quality(quality)
+        .with_password(password);
+    soop.parse().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_channel() {
+        assert_eq!(
+            resolve_channel("https://play.sooplive.co.kr/phonics1/281234567").unwrap(),
+            ("phonics1".to_string(), "281234567".to_string())
+        );
+        assert_eq!(
+            resolve_channel("https://play.afreecatv.com/phonics1/281234567").unwrap(),
+            ("phonics1".to_string(), "281234567".to_string())
+        );
+        assert_eq!(
+            resolve_channel("https://play.sooplive.co.kr/phonics1").unwrap(),
+            ("phonics1".to_string(), String::new())
+        );
+        assert!(resolve_channel("https://play.sooplive.co.kr/").is_err());
+        assert!(resolve_channel("https://www.twitch.tv/phonics1").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+mod state;
+
+use reqwest::StatusCode;
+use serde_json::Value;
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
+use crate::network::http::Client;
+use crate::parsers::{ParsedResult, Parser};
+use crate::platform::Platform;
+
+use self::state::{
+    extract_sigi_state, is_region_blocked, parse_live_room, parse_stream_url, StreamUrl,
+};
+
+const LIVE_BASE_URL: &str = "https://www.tiktok.com/@";
+const ROOM_INFO_API: &str = "https://webcast.tiktok.com/webcast/room/info/";
+
+pub struct TiktokParser {
+    unique_id: String,
+    http_client: Client,
+}
+
+impl TiktokParser {
+    fn new(unique_id: String) -> Self {
+        Self {
+            unique_id,
+            http_client: Client::new(),
+        }
+    }
+
+    async fn fetch_page(&self) -> LsarResult<String> {
+        let url = format!("{}{}/live", LIVE_BASE_URL, self.unique_id);
+        let response = self.http_client.get(&url).await?;
+
+        // 部分地区直接返回 451 或 403
+        let status = response.status();
+        if status == StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS || status == StatusCode::FORBIDDEN {
+            warn!("TikTok page returned {}", status);
+            return Err(RoomStateError::Restricted.into());
+        }
+
+        response.text().await.map_err(|e| {
+            error!("Failed to read TikTok page: {}", e);
+            LsarError::Http(e.into())
+        })
+    }
+
+    /// 页面中的 `stream_data` 为空时，通过房间信息接口获取直播流
+    async fn fetch_stream_url(&self, room_id: &str) -> LsarResult<StreamUrl> {
+        let mut url = Url::parse(ROOM_INFO_API)?;
+        url.query_pairs_mut()
+            .append_pair("aid", "1988")
+            .append_pair("room_id", room_id);
+
+        let resp: Value = self.http_client.get_json(url.as_str(), None).await?;
+        trace!("TikTok room info response: {}", resp);
+
+        let status_code = resp["status_code"].as_i64().unwrap_or(-1);
+        if status_code != 0 {
+            let message = resp["data"]["message"].as_str().unwrap_or_default();
+            error!("TikTok room info API failed ({}): {}", status_code, message);
+            if is_region_blocked(message) {
+                return Err(RoomStateError::Restricted.into());
+            }
+            return Err(LsarError::Other(message.to_string()));
+        }
+
+        let stream_url = &resp["data"]["stream_url"];
+        if stream_url.is_null() {
+            return Err(RoomStateError::Offline.into());
+        }
+
+        Ok(serde_json::from_value(stream_url.clone())?)
+    }
+}
+
+impl Parser for TiktokParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let html = self.fetch_page().await?;
+        let state = extract_sigi_state(&html)?;
+        let mut room = parse_live_room(&state)?;
+
+        if room.links.is_empty() {
+            if room.room_id.is_empty() {
+                return Err(RoomStateError::Offline.into());
+            }
+            let stream_url = self.fetch_stream_url(&room.room_id).await?;
+            (room.links, room.qualities) = parse_stream_url(&stream_url);
+        }
+
+        if room.links.is_empty() {
+            info!("TikTok room has no stream");
+            return Err(RoomStateError::Offline.into());
+        }
+        debug!(
+            anchor = room.anchor,
+            links = room.links.len(),
+            "Parsed TikTok room"
+        );
+
+        Ok(ParsedResult {
+            platform: Platform::Tiktok,
+            title: room.title,
+            anchor: room.anchor,
+            room_id: room.unique_id,
+            category: String::new(),
+            links: room.links,
+            qualities: room.qualities,
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+}
+
+/// 从 `tiktok.com/@<用户名>/live` 或主页链接中取出用户名
+fn resolve_unique_id(input: &str) -> LsarResult<String> {
+    let url = Url::parse(input)?;
+    let host = url.host_str().unwrap_or_default();
+    if host != "tiktok.com" && !host.ends_with(".tiktok.com") {
+        error!("Unsupported TikTok URL: {}", url);
+        return Err(LsarError::Other(format!("不支持的 TikTok 链接: {}", url)));
+    }
+
+    url.path_segments()
+        .into_iter()
+        .flatten()
+        .find_map(|s| s.strip_prefix('@'))
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| MissKeyFieldError::RoomId.into())
+}
+
+#[tauri::command]
+pub async fn parse_tiktok(url: String) -> LsarResult<ParsedResult> {
+    let unique_id = resolve_unique_id(&url)?;
+    info!("Parsing TikTok live: {}", unique_id);
+
+    let mut tiktok = TiktokParser::new(unique_id);
+    tiktok.parse().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_unique_id() {
+        assert_eq!(
+            resolve_unique_id("https://www.tiktok.com/@tester/live").unwrap(),
+            "tester"
+        );
+        assert_eq!(
+            resolve_unique_id("https://m.tiktok.com/@tester.live_01?lang=en").unwrap(),
+            "tester.live_01"
+        );
+        assert!(resolve_unique_id("https://www.tiktok.com/live").is_err());
+        assert!(resolve_unique_id("https://www.douyin.com/@tester").is_err());
+    }
+}
@@ -0,0 +1,291 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
+use crate::parsers::{Link, Quality};
+
+/// `liveRoom.status` 为 2 时正在直播，4 为未开播
+const STATUS_LIVE: i64 = 2;
+
+/// 已知画质从高到低排列：`stream_data` 中的键、房间信息接口 `stream_url` 中的键、名称，
+/// 两种来源的画质等级都按此顺序计算，排在最前的原画等级最高
+const QUALITIES: [(&str, &str, &str); 5] = [
+    ("origin", "ORIGIN", "原画"),
+    ("uhd", "FULL_HD1", "蓝光"),
+    ("hd", "HD1", "超清"),
+    ("sd", "SD1", "高清"),
+    ("ld", "SD2", "标清"),
+];
+
+/// 第 `index` 个已知画质的等级
+fn quality_level(index: usize) -> i64 {
+    (QUALITIES.len() - index) as i64
+}
+
+/// 所在地区无法访问 TikTok 时页面中的提示
+const REGION_BLOCKED_HINTS: [&str; 2] = [
+    "not available in your region",
+    "not available in your country",
+];
+
+/// 房间信息接口的 `stream_url`，键与抖音的 `Resolution` 类似，但多了 `ORIGIN`
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct StreamUrl {
+    #[serde(default)]
+    flv_pull_url: HashMap<String, String>,
+    #[serde(default)]
+    hls_pull_url_map: HashMap<String, String>,
+}
+
+/// 从页面的 `SIGI_STATE` 中解析出的直播间信息
+#[derive(Debug)]
+pub(super) struct LiveRoom {
+    pub anchor: String,
+    pub title: String,
+    /// 主播的用户名（uniqueId），即链接中 `@` 后的部分
+    pub unique_id: String,
+    /// 数字房间号，`stream_data` 为空时用于请求房间信息接口
+    pub room_id: String,
+    pub links: Vec<Link>,
+    pub qualities: Vec<Quality>,
+}
+
+/// 提取页面中的 `SIGI_STATE`，地区受限时页面中没有该脚本
+pub(super) fn extract_sigi_state(html: &str) -> LsarResult<Value> {
+    let re = Regex::new(r#"(?s)<script id="SIGI_STATE"[^>]*>(.*?)</script>"#)?;
+    let state = re
+        .captures(html)
+        .and_then(|caps| caps.get(1))
+        .ok_or_else(|| {
+            if is_region_blocked(html) {
+                warn!("TikTok is not available in current region");
+                LsarError::from(RoomStateError::Restricted)
+            } else {
+                error!("Failed to find SIGI_STATE in TikTok page");
+                LsarError::from("Failed to find SIGI_STATE")
+            }
+        })?
+        .as_str();
+
+    Ok(serde_json::from_str(state)?)
+}
+
+pub(super) fn is_region_blocked(html: &str) -> bool {
+    let html = html.to_lowercase();
+    REGION_BLOCKED_HINTS.iter().any(|hint| html.contains(hint))
+}
+
+pub(super) fn parse_live_room(state: &Value) -> LsarResult<LiveRoom> {
+    let info = &state["LiveRoom"]["liveRoomUserInfo"];
+    let user = &info["user"];
+    if user.is_null() {
+        info!("TikTok user does not exist");
+        return Err(RoomStateError::NotExists.into());
+    }
+
+    let room = &info["liveRoom"];
+    let status = room["status"]
+        .as_i64()
+        .or_else(|| user["status"].as_i64())
+        .unwrap_or_default();
+    if status != STATUS_LIVE {
+        info!("TikTok room is offline, status: {}", status);
+        return Err(RoomStateError::Offline.into());
+    }
+
+    let string = |value: &Value| value.as_str().unwrap_or_default().to_string();
+    let pull_data = &room["streamData"]["pull_data"];
+    let (links, qualities) = pull_data["stream_data"]
+        .as_str()
+        .map(|data| parse_stream_data(data, &pull_data["options"]["qualities"]))
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(LiveRoom {
+        anchor: string(&user["nickname"]),
+        title: string(&room["title"]),
+        unique_id: user["uniqueId"]
+            .as_str()
+            .ok_or(MissKeyFieldError::RoomId)?
+            .to_string(),
+        room_id: string(&user["roomId"]),
+        links,
+        qualities,
+    })
+}
+
+/// `stream_data` 是 JSON 字符串，`data` 中每个键是一种画质，`options.qualities` 提供名称，
+/// 其中的 `level` 与 `stream_url` 的画质无法对应，等级统一按 `QUALITIES` 的顺序计算
+fn parse_stream_data(data: &str, options: &Value) -> LsarResult<(Vec<Link>, Vec<Quality>)> {
+    let data: Value = serde_json::from_str(data)?;
+    let Some(streams) = data["data"].as_object() else {
+        return Ok(Default::default());
+    };
+
+    let options = options.as_array().map(Vec::as_slice).unwrap_or_default();
+    let mut qualities: Vec<(Quality, &str)> = streams
+        .keys()
+        .filter_map(|key| {
+            // 仅音频（ao）等未知的键不是视频画质
+            let index = QUALITIES.iter().position(|(k, _, _)| k == key)?;
+            let name = options
+                .iter()
+                .find(|o| o["sdk_key"] == key.as_str())
+                .and_then(|o| o["name"].as_str())
+                .unwrap_or(QUALITIES[index].2);
+            Some((Quality::new(quality_level(index), name), key.as_str()))
+        })
+        .collect();
+    qualities.sort_by_key(|(q, _)| Reverse(q.value));
+
+    let mut links = Vec::new();
+    for (quality, key) in &qualities {
+        let main = &streams[*key]["main"];
+        for (field, format) in [("flv", "FLV"), ("hls", "HLS")] {
+            if let Some(url) = main[field].as_str().filter(|u| !u.is_empty()) {
+                links.push(Link::new(url).quality(&quality.name).format(format));
+            }
+        }
+    }
+
+    Ok((links, qualities.into_iter().map(|(q, _)| q).collect()))
+}
+
+/// 按 `QUALITIES` 的顺序取各画质的链接，忽略未知的键
+pub(super) fn parse_stream_url(stream_url: &StreamUrl) -> (Vec<Link>, Vec<Quality>) {
+    let mut links = Vec::new();
+    let mut qualities = Vec::new();
+
+    for (i, (_, key, name)) in QUALITIES.iter().enumerate() {
+        let flv = stream_url.flv_pull_url.get(*key);
+        let hls = stream_url.hls_pull_url_map.get(*key);
+        if flv.is_none() && hls.is_none() {
+            continue;
+        }
+
+        qualities.push(Quality::new(quality_level(i), *name));
+        if let Some(url) = flv {
+            links.push(Link::new(url).quality(*name).format("FLV"));
+        }
+        if let Some(url) = hls {
+            links.push(Link::new(url).quality(*name).format("HLS"));
+        }
+    }
+
+    (links, qualities)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn state(status: i64, stream_data: Option<&str>) -> Value {
+        json!({
+            "LiveRoom": {
+                "liveRoomUserInfo": {
+                    "user": {
+                        "nickname": "Tester",
+                        "uniqueId": "tester",
+                        "roomId": "7312345678901234567",
+                        "status": status
+                    },
+                    "liveRoom": {
+                        "title": "hello",
+                        "status": status,
+                        "streamData": {
+                            "pull_data": {
+                                "options": {
+                                    "qualities": [
+                                        {"sdk_key": "origin", "name": "Original", "level": 10},
+                                        {"sdk_key": "sd", "name": "480p", "level": 2}
+                                    ]
+                                },
+                                "stream_data": stream_data
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_extract_sigi_state() {
+        let html = r#"<html><script id="SIGI_STATE" type="application/json">{"LiveRoom":{}}</script></html>"#;
+        assert!(extract_sigi_state(html).unwrap()["LiveRoom"].is_object());
+
+        let html = "<p>This LIVE is Not available in your region</p>";
+        assert!(matches!(
+            extract_sigi_state(html),
+            Err(LsarError::RoomState(RoomStateError::Restricted))
+        ));
+        assert!(extract_sigi_state("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_parse_live_room() {
+        let stream_data = json!({
+            "data": {
+                "sd": {"main": {"flv": "https://pull.tiktok/sd.flv", "hls": ""}},
+                "origin": {"main": {
+                    "flv": "https://pull.tiktok/origin.flv",
+                    "hls": "https://pull.tiktok/origin.m3u8"
+                }},
+                "hd": {"main": {"flv": "https://pull.tiktok/hd.flv"}},
+                "ao": {"main": {"flv": "https://pull.tiktok/ao.flv"}}
+            }
+        })
+        .to_string();
+        let room = parse_live_room(&state(2, Some(&stream_data))).unwrap();
+
+        assert_eq!(room.anchor, "Tester");
+        assert_eq!(room.unique_id, "tester");
+        assert_eq!(room.room_id, "7312345678901234567");
+        let names: Vec<_> = room.qualities.iter().map(|q| q.name.as_str()).collect();
+        assert_eq!(names, ["Original", "超清", "480p"]);
+        let levels: Vec<_> = room.qualities.iter().map(|q| q.value).collect();
+        assert_eq!(levels, [5, 3, 2]);
+        assert_eq!(room.links.len(), 4);
+        assert_eq!(room.links[0].url, "https://pull.tiktok/origin.flv");
+        assert_eq!(room.links[1].format.as_deref(), Some("HLS"));
+
+        let room = parse_live_room(&state(2, None)).unwrap();
+        assert!(room.links.is_empty());
+
+        assert!(matches!(
+            parse_live_room(&state(4, None)),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+        assert!(matches!(
+            parse_live_room(&json!({"LiveRoom": {}})),
+            Err(LsarError::RoomState(RoomStateError::NotExists))
+        ));
+    }
+
+    #[test]
+    fn test_parse_stream_url() {
+        let stream_url: StreamUrl = serde_json::from_value(json!({
+            "flv_pull_url": {
+                "HD1": "https://pull.tiktok/hd.flv",
+                "FULL_HD1": "https://pull.tiktok/uhd.flv",
+                "ORIGIN": "https://pull.tiktok/origin.flv"
+            },
+            "hls_pull_url_map": {"FULL_HD1": "https://pull.tiktok/uhd.m3u8"}
+        }))
+        .unwrap();
+        let (links, qualities) = parse_stream_url(&stream_url);
+
+        let names: Vec<_> = qualities.iter().map(|q| q.name.as_str()).collect();
+        assert_eq!(names, ["原画", "蓝光", "超清"]);
+        assert_eq!(qualities[0].value, 5);
+        assert_eq!(links.len(), 4);
+        assert_eq!(links[0].url, "https://pull.tiktok/origin.flv");
+        assert_eq!(links[3].url, "https://pull.tiktok/hd.flv");
+    }
+}
@@ -0,0 +1,204 @@
+mod page;
+mod stream_server;
+
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, SET_COOKIE};
+use reqwest::redirect::Policy;
+use serde_json::Value;
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
+use crate::network::http::Client;
+use crate::parsers::{ParsedResult, Parser};
+use crate::platform::Platform;
+use crate::utils::md5;
+
+use self::page::{parse_page, Lock};
+use self::stream_server::parse_stream_server;
+
+const BASE_URL: &str = "https://twitcasting.tv/";
+const STREAM_SERVER_API: &str = "https://twitcasting.tv/streamserver.php";
+
+pub struct TwitcastingParser {
+    user_id: String,
+    /// 加密直播的密码
+    password: String,
+    http_client: Client,
+}
+
+impl TwitcastingParser {
+    fn new(user_id: String) -> Self {
+        Self {
+            user_id,
+            password: String::new(),
+            http_client: Client::new(),
+        }
+    }
+
+    fn with_password(mut self, password: Option<String>) -> Self {
+        self.password = password.unwrap_or_default();
+        self
+    }
+
+    /// 直播密码以 MD5 形式通过 `word` 参数传递
+    fn word(&self) -> Option<String> {
+        (!self.password.is_empty()).then(|| md5(self.password.clone()))
+    }
+
+    async fn get_stream_server(&self) -> LsarResult<Value> {
+        let mut url = Url::parse(STREAM_SERVER_API)?;
+        url.query_pairs_mut()
+            .append_pair("target", &self.user_id)
+            .append_pair("mode", "client")
+            .append_pair("player", "pc_web");
+        if let Some(word) = self.word() {
+            url.query_pairs_mut().append_pair("word", &word);
+        }
+
+        let resp: Value = self.http_client.get_json(url.as_str(), None).await?;
+        trace!("TwitCasting stream server response: {}", resp);
+
+        Ok(resp)
+    }
+
+    /// 有密码时像网页一样提交密码表单，密码正确时返回的页面中不再有密码输入框
+    async fn get_page(&self) -> LsarResult<String> {
+        let url = format!("{}{}", BASE_URL, self.user_id);
+        if self.password.is_empty() {
+            return self.http_client.get_text(&url).await;
+        }
+
+        // 验证通过后会跳转回直播页面，并用 cookie 记住已验证，跳转时需要带上 cookie
+        let client = reqwest::Client::builder()
+            .redirect(Policy::none())
+            .build()?;
+        let request = client.post(&url).form(&[("password", &self.password)]);
+        let response = self.http_client.send_request(request, None).await?;
+        if !response.status().is_redirection() {
+            return Ok(response.text().await?);
+        }
+
+        let cookie = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok()?.split(';').next())
+            .collect::<Vec<_>>()
+            .join("; ");
+        debug!("TwitCasting password form redirected");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&cookie).map_err(|e| LsarError::from(e.to_string()))?,
+        );
+        let response = self
+            .http_client
+            .send_request(self.http_client.inner.get(&url), Some(headers))
+            .await?;
+        Ok(response.text().await?)
+    }
+}
+
+/// 提交密码后页面仍要求输入密码，说明密码错误
+fn check_lock(lock: Option<Lock>, has_password: bool) -> LsarResult<()> {
+    match lock {
+        Some(Lock::MembersOnly) => Err(RoomStateError::MembersOnly.into()),
+        Some(Lock::Password) if has_password => {
+            info!("TwitCasting live password is wrong");
+            Err(RoomStateError::WrongPassword.into())
+        }
+        Some(Lock::Password) => Err(RoomStateError::PasswordRequired.into()),
+        None => Ok(()),
+    }
+}
+
+impl Parser for TwitcastingParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let html = self.get_page().await?;
+        let page = parse_page(&html)?;
+        let word = self.word();
+        check_lock(page.lock, word.is_some())?;
+
+        let resp = self.get_stream_server().await?;
+        let (links, qualities) = parse_stream_server(&resp, word.as_deref())?;
+
+        Ok(ParsedResult {
+            platform: Platform::Twitcasting,
+            title: page.title,
+            anchor: page.anchor.unwrap_or_else(|| self.user_id.clone()),
+            room_id: self.user_id.clone(),
+            category: String::new(),
+            links,
+            qualities,
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+}
+
+/// 从 `twitcasting.tv/<用户 ID>` 或其子页面链接中取出用户 ID
+fn resolve_user_id(input: &str) -> LsarResult<String> {
+    let url = Url::parse(input)?;
+    let host = url.host_str().unwrap_or_default();
+    if host != "twitcasting.tv" && !host.ends_with(".twitcasting.tv") {
+        error!("Unsupported TwitCasting URL: {}", url);
+        return Err(LsarError::Other(format!(
+            "不支持的 TwitCasting 链接: {}",
+            url
+        )));
+    }
+
+    // 部分用户 ID 带有 `c:`、`g:` 等前缀
+    url.path_segments()
+        .and_then(|mut s| s.next())
+        .filter(|s| !s.is_empty() && !s.ends_with(".php"))
+        .map(|s| s.replace("%3A", ":"))
+        .ok_or_else(|| MissKeyFieldError::RoomId.into())
+}
+
+#[tauri::command]
+pub async fn parse_twitcasting(url: String, password: Option<String>) -> LsarResult<ParsedResult> {
+    let user_id = resolve_user_id(&url)?;
+    info!("Parsing TwitCasting live: {}", user_id);
+
+    let mut twitcasting = TwitcastingParser::new(user_id).with_password(password);
+    twitcasting.parse().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_user_id() {
+        assert_eq!(
+            resolve_user_id("https://twitcasting.tv/twitcasting_jp").unwrap(),
+            "twitcasting_jp"
+        );
+        assert_eq!(
+            resolve_user_id("https://twitcasting.tv/c:tester/movie/812345678").unwrap(),
+            "c:tester"
+        );
+        assert!(resolve_user_id("https://twitcasting.tv/").is_err());
+        assert!(resolve_user_id("https://example.com/twitcasting_jp").is_err());
+    }
+
+    #[test]
+    fn test_check_lock() {
+        assert!(check_lock(None, false).is_ok());
+        assert!(check_lock(None, true).is_ok());
+        assert!(matches!(
+            check_lock(Some(Lock::Password), false),
+            Err(LsarError::RoomState(RoomStateError::PasswordRequired))
+        ));
+        assert!(matches!(
+            check_lock(Some(Lock::Password), true),
+            Err(LsarError::RoomState(RoomStateError::WrongPassword))
+        ));
+        assert!(matches!(
+            check_lock(Some(Lock::MembersOnly), true),
+            Err(LsarError::RoomState(RoomStateError::MembersOnly))
+        ));
+    }
+}
@@ -0,0 +1,92 @@
+use regex::Regex;
+
+use crate::error::LsarResult;
+
+/// 会员限定直播在播放器位置显示的锁定提示
+const MEMBERS_ONLY_HINTS: [&str; 2] = ["メンバー限定", "メンバーシップ限定"];
+
+/// 直播页面的访问限制
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Lock {
+    /// 需要输入直播密码
+    Password,
+    /// 仅限会员观看
+    MembersOnly,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Page {
+    pub title: String,
+    /// 主播的显示名称，页面中没有时为 `None`
+    pub anchor: Option<String>,
+    pub lock: Option<Lock>,
+}
+
+/// 从直播页面的 `og:title` 中取标题、从导航栏中取主播名称，并检查播放器是否被锁定
+pub(super) fn parse_page(html: &str) -> LsarResult<Page> {
+    let capture = |pattern: &str| -> LsarResult<Option<String>> {
+        Ok(Regex::new(pattern)?
+            .captures(html)
+            .and_then(|caps| caps.get(1))
+            .map(|m| m.as_str().trim().to_string()))
+    };
+
+    let title = capture(r#"<meta property="og:title" content="([^"]*)""#)?.unwrap_or_default();
+    let anchor = capture(r#"<span class="tw-user-nav-name">([^<]+)</span>"#)?
+        .filter(|name| !name.is_empty());
+
+    // 只检查播放器的锁定提示，标题、简介和评论中也可能出现“メンバー限定”
+    let player_lock =
+        capture(r#"(?s)<div class="[^"]*\btw-player-page-lock\b[^"]*"[^>]*>(.*?)</div>"#)?;
+    let members_only =
+        player_lock.is_some_and(|text| MEMBERS_ONLY_HINTS.iter().any(|h| text.contains(h)));
+    let lock = if members_only {
+        Some(Lock::MembersOnly)
+    } else if Regex::new(r#"<input[^>]+name="password""#)?.is_match(html) {
+        Some(Lock::Password)
+    } else {
+        None
+    };
+
+    Ok(Page {
+        title,
+        anchor,
+        lock,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_page() {
+        let html = r#"<meta property="og:title" content="雑談配信">
+            <span class="tw-user-nav-name">ツイキャス公式</span><p>hello</p>"#;
+        assert_eq!(
+            parse_page(html).unwrap(),
+            Page {
+                title: "雑談配信".to_string(),
+                anchor: Some("ツイキャス公式".to_string()),
+                lock: None
+            }
+        );
+
+        let html = r#"<form method="post"><input type="password" name="password"></form>"#;
+        let page = parse_page(html).unwrap();
+        assert_eq!(page.lock, Some(Lock::Password));
+        assert_eq!(page.anchor, None);
+
+        let html = r#"<div class="tw-player-page-lock tw-player-page-lock--membership">
+            <p>このライブはメンバー限定です</p></div>"#;
+        assert_eq!(parse_page(html).unwrap().lock, Some(Lock::MembersOnly));
+    }
+
+    #[test]
+    fn test_parse_page_members_only_outside_player() {
+        // 标题和评论中提到会员限定，但播放器没有被锁定
+        let html = r#"<meta property="og:title" content="次回はメンバー限定配信">
+            <div class="tw-comment-item">メンバーシップ限定の配信もあります</div>"#;
+        assert_eq!(parse_page(html).unwrap().lock, None);
+    }
+}
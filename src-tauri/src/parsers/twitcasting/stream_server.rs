@@ -0,0 +1,121 @@
+use serde_json::Value;
+use url::Url;
+
+use crate::error::{LsarResult, RoomStateError};
+use crate::parsers::{Link, Quality};
+
+/// `tc-hls.streams` 中的画质，值越大画质越高
+const HLS_QUALITIES: [(&str, i64, &str); 3] = [
+    ("high", 3, "高画质"),
+    ("medium", 2, "中画质"),
+    ("low", 1, "低画质"),
+];
+
+/// `llfmp4.streams` 中的 WebSocket 流，`main` 为原画
+const FMP4_STREAMS: [(&str, &str); 3] = [
+    ("main", "原画"),
+    ("mobilesource", "移动端原画"),
+    ("base", "低画质"),
+];
+
+/// 解析 `streamserver.php` 的响应，`word` 是直播密码的 MD5，需附加到每个链接上
+pub(super) fn parse_stream_server(
+    resp: &Value,
+    word: Option<&str>,
+) -> LsarResult<(Vec<Link>, Vec<Quality>)> {
+    let movie = &resp["movie"];
+    if movie["id"].is_null() {
+        info!("TwitCasting user does not exist");
+        return Err(RoomStateError::NotExists.into());
+    }
+    if !movie["live"].as_bool().unwrap_or(false) {
+        info!("TwitCasting user is offline");
+        return Err(RoomStateError::Offline.into());
+    }
+
+    let with_word = |url: &str| -> LsarResult<String> {
+        let mut url = Url::parse(url)?;
+        if let Some(word) = word {
+            url.query_pairs_mut().append_pair("word", word);
+        }
+        Ok(url.into())
+    };
+
+    let mut links = Vec::new();
+    let mut qualities = Vec::new();
+
+    let hls = &resp["tc-hls"]["streams"];
+    for (key, value, name) in HLS_QUALITIES {
+        if let Some(url) = hls[key].as_str() {
+            links.push(Link::new(with_word(url)?).quality(name).format("HLS"));
+            qualities.push(Quality::new(value, name));
+        }
+    }
+
+    let fmp4 = &resp["llfmp4"]["streams"];
+    for (key, name) in FMP4_STREAMS {
+        if let Some(url) = fmp4[key].as_str() {
+            links.push(
+                Link::new(with_word(url)?)
+                    .quality(name)
+                    .format("fMP4 (WebSocket)"),
+            );
+        }
+    }
+
+    if links.is_empty() {
+        warn!("No stream in TwitCasting response: {}", resp);
+        return Err(RoomStateError::Offline.into());
+    }
+
+    Ok((links, qualities))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::error::LsarError;
+
+    #[test]
+    fn test_parse_stream_server() {
+        let resp = json!({
+            "movie": {"id": 812345678, "live": true},
+            "tc-hls": {"streams": {
+                "high": "https://tc-hls.twitcasting.tv/high/master.m3u8",
+                "low": "https://tc-hls.twitcasting.tv/low/master.m3u8"
+            }},
+            "llfmp4": {"streams": {
+                "main": "wss://llfmp4.twitcasting.tv/ws.app/stream/812345678/fmp4/bd/1/1500?mode=main",
+                "base": "wss://llfmp4.twitcasting.tv/ws.app/stream/812345678/fmp4/bd/1/1500?mode=base"
+            }}
+        });
+
+        let (links, qualities) = parse_stream_server(&resp, None).unwrap();
+        assert_eq!(links.len(), 4);
+        assert_eq!(links[0].quality.as_deref(), Some("高画质"));
+        assert_eq!(links[2].format.as_deref(), Some("fMP4 (WebSocket)"));
+        assert_eq!(qualities.len(), 2);
+        assert_eq!(qualities[1].value, 1);
+
+        let (links, _) = parse_stream_server(&resp, Some("0123abcd")).unwrap();
+        assert_eq!(
+            links[0].url,
+            "https://tc-hls.twitcasting.tv/high/master.m3u8?word=0123abcd"
+        );
+        assert!(links[2].url.ends_with("?mode=main&word=0123abcd"));
+    }
+
+    #[test]
+    fn test_parse_stream_server_state() {
+        assert!(matches!(
+            parse_stream_server(&json!({"movie": {"id": 1, "live": false}}), None),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+        assert!(matches!(
+            parse_stream_server(&json!({}), None),
+            Err(LsarError::RoomState(RoomStateError::NotExists))
+        ));
+    }
+}
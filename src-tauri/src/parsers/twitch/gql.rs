@@ -0,0 +1,211 @@
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::{json, Value};
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
+use crate::network::http::Client;
+
+const GQL_URL: &str = "https://gql.twitch.tv/gql";
+
+/// 网页端播放器的公开 Client-ID
+const CLIENT_ID: &str = "kimne78kx3ncx6brgo4mv6wki5h1ko";
+
+const ACCESS_TOKEN_QUERY: &str = r#"query PlaybackAccessToken_Template($login: String!, $isLive: Boolean!, $vodID: ID!, $isVod: Boolean!, $playerType: String!) { streamPlaybackAccessToken(channelName: $login, params: {platform: "web", playerBackend: "mediaplayer", playerType: $playerType}) @include(if: $isLive) { value signature authorization { isForbidden forbiddenReasonCode } __typename } videoPlaybackAccessToken(id: $vodID, params: {platform: "web", playerBackend: "mediaplayer", playerType: $playerType}) @include(if: $isVod) { value signature __typename } }"#;
+
+const CHANNEL_QUERY: &str = "query ChannelInfo($login: String!) { user(login: $login) { login displayName broadcastSettings { title game { displayName } } stream { id } } }";
+
+/// 播放 usher 主播放列表需要的凭证
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct AccessToken {
+    pub value: String,
+    pub signature: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Channel {
+    pub login: String,
+    pub display_name: String,
+    pub title: String,
+    pub category: String,
+}
+
+pub(super) struct GqlClient<'a> {
+    client: &'a Client,
+    url: String,
+}
+
+impl<'a> GqlClient<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self::with_base_url(client, GQL_URL)
+    }
+
+    pub fn with_base_url(client: &'a Client, url: &str) -> Self {
+        Self {
+            client,
+            url: url.to_string(),
+        }
+    }
+
+    /// 同一次请求中查询频道信息和播放凭证
+    pub async fn fetch(&self, login: &str) -> LsarResult<(Channel, AccessToken)> {
+        debug!("Fetching Twitch channel and access token for: {}", login);
+        let body = json!([
+            {
+                "operationName": "ChannelInfo",
+                "query": CHANNEL_QUERY,
+                "variables": { "login": login }
+            },
+            {
+                "operationName": "PlaybackAccessToken_Template",
+                "query": ACCESS_TOKEN_QUERY,
+                "variables": {
+                    "isLive": true,
+                    "login": login,
+                    "isVod": false,
+                    "vodID": "",
+                    "playerType": "site"
+                }
+            }
+        ]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Client-ID", HeaderValue::from_static(CLIENT_ID));
+
+        let resp: Value = self
+            .client
+            .send_request(self.client.inner.post(&self.url).json(&body), Some(headers))
+            .await?
+            .json()
+            .await?;
+        trace!("Twitch GQL response: {}", resp);
+
+        Ok((parse_channel(&resp[0])?, parse_access_token(&resp[1])?))
+    }
+}
+
+fn parse_channel(resp: &Value) -> LsarResult<Channel> {
+    let user = &resp["data"]["user"];
+    if user.is_null() {
+        info!("Twitch channel does not exist");
+        return Err(RoomStateError::NotExists.into());
+    }
+
+    if user["stream"].is_null() {
+        info!("Twitch channel is offline");
+        return Err(RoomStateError::Offline.into());
+    }
+
+    let settings = &user["broadcastSettings"];
+    Ok(Channel {
+        login: user["login"]
+            .as_str()
+            .ok_or(MissKeyFieldError::RoomId)?
+            .to_string(),
+        display_name: user["displayName"]
+            .as_str()
+            .ok_or(MissKeyFieldError::AnchorName)?
+            .to_string(),
+        title: settings["title"].as_str().unwrap_or_default().to_string(),
+        category: settings["game"]["displayName"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+fn parse_access_token(resp: &Value) -> LsarResult<AccessToken> {
+    let token = &resp["data"]["streamPlaybackAccessToken"];
+
+    if token["authorization"]["isForbidden"]
+        .as_bool()
+        .unwrap_or(false)
+    {
+        warn!(
+            "Twitch playback is forbidden: {}",
+            token["authorization"]["forbiddenReasonCode"]
+        );
+        return Err(RoomStateError::Restricted.into());
+    }
+
+    match (token["value"].as_str(), token["signature"].as_str()) {
+        (Some(value), Some(signature)) => Ok(AccessToken {
+            value: value.to_string(),
+            signature: signature.to_string(),
+        }),
+        _ => {
+            error!("Failed to get Twitch access token: {}", resp);
+            Err(LsarError::from("Failed to get Twitch access token"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn channel(stream: Value) -> Value {
+        json!({
+            "data": {
+                "user": {
+                    "login": "shroud",
+                    "displayName": "shroud",
+                    "broadcastSettings": {"title": "ranked", "game": {"displayName": "VALORANT"}},
+                    "stream": stream
+                }
+            }
+        })
+    }
+
+    fn token(forbidden: bool) -> Value {
+        json!({
+            "data": {
+                "streamPlaybackAccessToken": {
+                    "value": "{\"channel\":\"shroud\"}",
+                    "signature": "abc123",
+                    "authorization": {"isForbidden": forbidden, "forbiddenReasonCode": "NONE"}
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("Client-ID", CLIENT_ID))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!([channel(json!({"id": "1"})), token(false)])),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let (channel, token) = GqlClient::with_base_url(&client, &server.uri())
+            .fetch("shroud")
+            .await
+            .unwrap();
+
+        assert_eq!(channel.display_name, "shroud");
+        assert_eq!(channel.category, "VALORANT");
+        assert_eq!(token.signature, "abc123");
+    }
+
+    #[test]
+    fn test_channel_and_token_states() {
+        assert!(matches!(
+            parse_channel(&channel(Value::Null)),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+        assert!(matches!(
+            parse_channel(&json!({"data": {"user": null}})),
+            Err(LsarError::RoomState(RoomStateError::NotExists))
+        ));
+        assert!(matches!(
+            parse_access_token(&token(true)),
+            Err(LsarError::RoomState(RoomStateError::Restricted))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+mod gql;
+
+use rand::Rng;
+use reqwest::StatusCode;
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RoomStateError};
+use crate::network::http::Client;
+use crate::parsers::hls::{into_links, parse_master_playlist};
+use crate::parsers::{ParsedResult, Parser};
+use crate::platform::Platform;
+
+use self::gql::{AccessToken, GqlClient};
+
+const USHER_URL: &str = "https://usher.ttvnw.net/api/channel/hls/";
+
+pub struct TwitchParser {
+    login: String,
+    http_client: Client,
+}
+
+impl TwitchParser {
+    fn new(login: String) -> Self {
+        Self {
+            login,
+            http_client: Client::new(),
+        }
+    }
+
+    fn usher_url(&self, token: &AccessToken) -> LsarResult<Url> {
+        let mut url = Url::parse(&format!("{}{}.m3u8", USHER_URL, self.login))?;
+        url.query_pairs_mut()
+            .append_pair("sig", &token.signature)
+            .append_pair("token", &token.value)
+            .append_pair("allow_source", "true")
+            .append_pair("fast_bread", "true")
+            .append_pair("player_backend", "mediaplayer")
+            .append_pair("playlist_include_framerate", "true")
+            .append_pair("supported_codecs", "av1,h265,h264")
+            .append_pair("p", &rand::rng().random_range(0..1_000_000u32).to_string());
+
+        Ok(url)
+    }
+}
+
+impl Parser for TwitchParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let (channel, token) = GqlClient::new(&self.http_client).fetch(&self.login).await?;
+
+        let url = self.usher_url(&token)?;
+        let response = self.http_client.get(url.as_str()).await?;
+        match response.status() {
+            // 频道刚下播时 GQL 中的 stream 可能还未清空
+            StatusCode::NOT_FOUND => return Err(RoomStateError::Offline.into()),
+            StatusCode::FORBIDDEN => return Err(RoomStateError::Restricted.into()),
+            _ => {}
+        }
+
+        let playlist = response.text().await?;
+        let (links, qualities) = into_links(parse_master_playlist(&url, &playlist)?);
+        debug!(
+            login = self.login,
+            links = links.len(),
+            "Parsed Twitch streams"
+        );
+
+        Ok(ParsedResult {
+            platform: Platform::Twitch,
+            title: channel.title,
+            anchor: channel.display_name,
+            room_id: channel.login,
+            category: channel.category,
+            links,
+            qualities,
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+}
+
+/// 从频道名或 `twitch.tv/<频道名>` 链接中取出小写的频道名
+fn resolve_login(input: &str) -> LsarResult<String> {
+    let login = match Url::parse(input) {
+        Ok(url) => {
+            let host = url.host_str().unwrap_or_default();
+            if host != "twitch.tv" && !host.ends_with(".twitch.tv") {
+                error!("Unsupported Twitch URL: {}", url);
+                return Err(LsarError::Other(format!("不支持的 Twitch 链接: {}", url)));
+            }
+
+            url.path_segments()
+                .and_then(|mut s| s.find(|s| !s.is_empty()))
+                .ok_or(MissKeyFieldError::RoomId)?
+                .to_string()
+        }
+        Err(_) => input.trim().to_string(),
+    };
+
+    let valid = (3..=25).contains(&login.len())
+        && login.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        error!("Invalid Twitch login: {}", login);
+        return Err(MissKeyFieldError::RoomId.into());
+    }
+
+    Ok(login.to_ascii_lowercase())
+}
+
+/// `url` 可以是频道名或频道链接
+#[tauri::command]
+pub async fn parse_twitch(url: String) -> LsarResult<ParsedResult> {
+    let login = resolve_login(&url)?;
+    info!("Parsing Twitch channel: {}", login);
+
+    let mut twitch = TwitchParser::new(login);
+    twitch.parse().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_login() {
+        assert_eq!(
+            resolve_login("https://www.twitch.tv/Shroud").unwrap(),
+            "shroud"
+        );
+        assert_eq!(
+            resolve_login("https://m.twitch.tv/kai_cenat?sr=a").unwrap(),
+            "kai_cenat"
+        );
+        assert_eq!(resolve_login("xqc").unwrap(), "xqc");
+        assert!(resolve_login("https://www.youtube.com/xqc").is_err());
+        assert!(resolve_login("https://www.twitch.tv/").is_err());
+        assert!(resolve_login("not a login").is_err());
+    }
+}
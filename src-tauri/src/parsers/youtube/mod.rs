@@ -0,0 +1,161 @@
+mod player_response;
+
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE};
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError};
+use crate::network::http::Client;
+use crate::parsers::hls::{into_links, parse_master_playlist};
+use crate::parsers::{ParsedResult, Parser};
+use crate::platform::Platform;
+
+use self::player_response::{extract_player_response, parse_live_video, LiveState};
+
+const BASE_URL: &str = "https://www.youtube.com/";
+
+/// 跳过欧盟地区的 cookie 同意页面
+const CONSENT_COOKIE: &str = "SOCS=CAI";
+
+pub struct YoutubeParser {
+    page_url: String,
+    http_client: Client,
+}
+
+impl YoutubeParser {
+    fn new(page_url: String) -> Self {
+        Self {
+            page_url,
+            http_client: Client::new(),
+        }
+    }
+
+    async fn get_page(&self) -> LsarResult<String> {
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, HeaderValue::from_static(CONSENT_COOKIE));
+
+        let response = self
+            .http_client
+            .send_request(self.http_client.inner.get(&self.page_url), Some(headers))
+            .await?;
+        Ok(response.text().await?)
+    }
+}
+
+impl Parser for YoutubeParser {
+    async fn parse(&mut self) -> LsarResult<ParsedResult> {
+        let html = self.get_page().await?;
+        let video = parse_live_video(&extract_player_response(&html)?)?;
+
+        let mut result = ParsedResult {
+            platform: Platform::Youtube,
+            title: video.title,
+            anchor: video.author,
+            room_id: video.channel_id,
+            category: video.category,
+            links: Vec::new(),
+            qualities: Vec::new(),
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        };
+
+        match video.state {
+            LiveState::Upcoming { start } => {
+                info!("YouTube live stream is scheduled at {}", start);
+                result.scheduled_start = Some(start);
+            }
+            LiveState::Live { hls_manifest_url } => {
+                let url = Url::parse(&hls_manifest_url)?;
+                let playlist = self.http_client.get_text(url.as_str()).await?;
+                (result.links, result.qualities) =
+                    into_links(parse_master_playlist(&url, &playlist)?);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// 将各种形式的 YouTube 链接转换为可以获取 `ytInitialPlayerResponse` 的页面地址
+///
+/// 支持的形式：
+/// - `youtube.com/@<handle>` 和 `youtube.com/@<handle>/live`
+/// - `youtube.com/channel/<频道 ID>`，历史记录中保存的是频道 ID
+/// - `youtube.com/watch?v=<视频 ID>`、`youtube.com/live/<视频 ID>` 和 `youtu.be/<视频 ID>`
+fn resolve_page_url(input: &str) -> LsarResult<String> {
+    let url = Url::parse(input)?;
+    let host = url.host_str().unwrap_or_default();
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|s| s.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    let watch_url = |id: &str| format!("{}watch?v={}", BASE_URL, id);
+
+    if host == "youtu.be" {
+        return segments
+            .first()
+            .map(|id| watch_url(id))
+            .ok_or_else(|| MissKeyFieldError::RoomId.into());
+    }
+
+    if host != "youtube.com" && !host.ends_with(".youtube.com") {
+        error!("Unsupported YouTube URL: {}", url);
+        return Err(LsarError::Other(format!("不支持的 YouTube 链接: {}", url)));
+    }
+
+    match segments.as_slice() {
+        ["watch", ..] => url
+            .query_pairs()
+            .find(|(k, _)| k == "v")
+            .map(|(_, v)| watch_url(&v))
+            .ok_or_else(|| MissKeyFieldError::RoomId.into()),
+        ["live", id, ..] => Ok(watch_url(id)),
+        [handle, ..] if handle.starts_with('@') => Ok(format!("{}{}/live", BASE_URL, handle)),
+        ["channel", id, ..] => Ok(format!("{}channel/{}/live", BASE_URL, id)),
+        _ => Err(MissKeyFieldError::RoomId.into()),
+    }
+}
+
+#[tauri::command]
+pub async fn parse_youtube(url: String) -> LsarResult<ParsedResult> {
+    let page_url = resolve_page_url(&url)?;
+    info!("Parsing YouTube live: {}", page_url);
+
+    let mut youtube = YoutubeParser::new(page_url);
+    youtube.parse().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_page_url() {
+        assert_eq!(
+            resolve_page_url("https://www.youtube.com/@LofiGirl/live").unwrap(),
+            "https://www.youtube.com/@LofiGirl/live"
+        );
+        assert_eq!(
+            resolve_page_url("https://m.youtube.com/@LofiGirl").unwrap(),
+            "https://www.youtube.com/@LofiGirl/live"
+        );
+        assert_eq!(
+            resolve_page_url("https://www.youtube.com/watch?v=jfKfPfyJRdk&t=1").unwrap(),
+            "https://www.youtube.com/watch?v=jfKfPfyJRdk"
+        );
+        assert_eq!(
+            resolve_page_url("https://youtu.be/jfKfPfyJRdk?si=abc").unwrap(),
+            "https://www.youtube.com/watch?v=jfKfPfyJRdk"
+        );
+        assert_eq!(
+            resolve_page_url("https://www.youtube.com/live/jfKfPfyJRdk").unwrap(),
+            "https://www.youtube.com/watch?v=jfKfPfyJRdk"
+        );
+        assert_eq!(
+            resolve_page_url("https://www.youtube.com/channel/UCSJ4gkVC6NrvII8umztf0Ow").unwrap(),
+            "https://www.youtube.com/channel/UCSJ4gkVC6NrvII8umztf0Ow/live"
+        );
+        assert!(resolve_page_url("https://www.youtube.com/").is_err());
+        assert!(resolve_page_url("https://www.twitch.tv/@a").is_err());
+    }
+}
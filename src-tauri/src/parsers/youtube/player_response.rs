@@ -0,0 +1,161 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError, RequestError, RoomStateError};
+
+/// 从 `ytInitialPlayerResponse` 中解析出的直播状态
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum LiveState {
+    /// 正在直播
+    Live { hls_manifest_url: String },
+    /// 预定的直播或首映，附带开始时间（Unix 时间戳，秒）
+    Upcoming { start: i64 },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct LiveVideo {
+    pub channel_id: String,
+    pub title: String,
+    pub author: String,
+    pub category: String,
+    pub state: LiveState,
+}
+
+/// 提取页面中的 `ytInitialPlayerResponse`，频道未开播时 `/live` 会显示频道主页，不含此对象
+pub(super) fn extract_player_response(html: &str) -> LsarResult<Value> {
+    let re = Regex::new(r"(?s)ytInitialPlayerResponse\s*=\s*(\{.+?\})\s*;\s*(?:var\s|</script>)")?;
+    let json = re
+        .captures(html)
+        .and_then(|caps| caps.get(1))
+        .ok_or_else(|| {
+            info!("No player response in YouTube page, channel is not live");
+            RoomStateError::Offline
+        })?
+        .as_str();
+
+    Ok(serde_json::from_str(json)?)
+}
+
+pub(super) fn parse_live_video(resp: &Value) -> LsarResult<LiveVideo> {
+    let details = &resp["videoDetails"];
+    let playability = &resp["playabilityStatus"];
+    let status = playability["status"].as_str().unwrap_or_default();
+
+    match status {
+        "OK" | "LIVE_STREAM_OFFLINE" => {}
+        "LOGIN_REQUIRED" => {
+            warn!("YouTube requires login: {}", playability["reason"]);
+            return Err(RequestError::LoginRequired.into());
+        }
+        _ => {
+            error!("YouTube video is not playable: {}", playability);
+            return Err(match playability["reason"].as_str() {
+                Some(reason) => LsarError::Other(reason.to_string()),
+                None => RoomStateError::NotExists.into(),
+            });
+        }
+    }
+
+    if !details["isLiveContent"].as_bool().unwrap_or(false) {
+        info!("YouTube video is not a live stream");
+        return Err(LsarError::from("该链接不是直播"));
+    }
+
+    let state = if details["isUpcoming"].as_bool().unwrap_or(false) {
+        let start = playability["liveStreamability"]["liveStreamabilityRenderer"]["offlineSlate"]
+            ["liveStreamOfflineSlateRenderer"]["scheduledStartTime"]
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| {
+                error!("Failed to find scheduled start time: {}", playability);
+                LsarError::from("Failed to find scheduled start time")
+            })?;
+        LiveState::Upcoming { start }
+    } else if details["isLive"].as_bool().unwrap_or(false) {
+        let hls_manifest_url = resp["streamingData"]["hlsManifestUrl"]
+            .as_str()
+            .ok_or_else(|| {
+                error!("Live video has no HLS manifest");
+                LsarError::from("Failed to find HLS manifest")
+            })?
+            .to_string();
+        LiveState::Live { hls_manifest_url }
+    } else {
+        // 已结束的直播，只剩回放
+        info!("YouTube live stream has ended");
+        return Err(RoomStateError::Offline.into());
+    };
+
+    let string = |value: &Value| value.as_str().map(str::to_string);
+    Ok(LiveVideo {
+        channel_id: string(&details["channelId"]).ok_or(MissKeyFieldError::RoomId)?,
+        title: string(&details["title"]).ok_or(MissKeyFieldError::Title)?,
+        author: string(&details["author"]).ok_or(MissKeyFieldError::AnchorName)?,
+        category: string(&resp["microformat"]["playerMicroformatRenderer"]["category"])
+            .unwrap_or_default(),
+        state,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn response(is_live: bool, is_upcoming: bool) -> Value {
+        json!({
+            "playabilityStatus": {
+                "status": if is_upcoming { "LIVE_STREAM_OFFLINE" } else { "OK" },
+                "liveStreamability": {
+                    "liveStreamabilityRenderer": {
+                        "offlineSlate": {
+                            "liveStreamOfflineSlateRenderer": {"scheduledStartTime": "1767225600"}
+                        }
+                    }
+                }
+            },
+            "streamingData": {"hlsManifestUrl": "https://manifest.googlevideo.com/api/manifest/hls_variant/id/1"},
+            "videoDetails": {
+                "videoId": "jfKfPfyJRdk",
+                "channelId": "UCSJ4gkVC6NrvII8umztf0Ow",
+                "title": "lofi hip hop radio",
+                "author": "Lofi Girl",
+                "isLiveContent": true,
+                "isLive": is_live,
+                "isUpcoming": is_upcoming
+            },
+            "microformat": {"playerMicroformatRenderer": {"category": "Music"}}
+        })
+    }
+
+    #[test]
+    fn test_extract_player_response() {
+        let html = r#"<script>var ytInitialPlayerResponse = {"videoDetails":{"videoId":"a"}};var meta = {};</script>"#;
+        assert_eq!(
+            extract_player_response(html).unwrap()["videoDetails"]["videoId"],
+            "a"
+        );
+        assert!(matches!(
+            extract_player_response("<html></html>"),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+    }
+
+    #[test]
+    fn test_parse_live_video() {
+        let video = parse_live_video(&response(true, false)).unwrap();
+        assert_eq!(video.channel_id, "UCSJ4gkVC6NrvII8umztf0Ow");
+        assert_eq!(video.author, "Lofi Girl");
+        assert_eq!(video.category, "Music");
+        assert!(matches!(video.state, LiveState::Live { .. }));
+
+        let video = parse_live_video(&response(false, true)).unwrap();
+        assert_eq!(video.state, LiveState::Upcoming { start: 1767225600 });
+
+        assert!(matches!(
+            parse_live_video(&response(false, false)),
+            Err(LsarError::RoomState(RoomStateError::Offline))
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+/// 网页端播放器版本，与 yy.com 当前使用的 SDK 保持一致
+pub(super) const CLIENT_VERSION: &str = "5.19.4";
+pub(super) const APP: &str = "yylive_web";
+/// 网页端业务 ID
+pub(super) const BID: &str = "120";
+pub(super) const HEAD_CLIENT_TYPE: u32 = 108;
+pub(super) const WEB_CLIENT_TYPE: u32 = 8;
+
+/// 未指定档位时请求的档位，与网页端默认一致
+pub(super) const DEFAULT_GEAR: u32 = 2;
+
+/// 档位及其名称，接口只返回档位编号
+pub(super) const GEARS: [(u32, &str); 5] = [
+    (1, "标清"),
+    (2, "高清"),
+    (3, "超清"),
+    (4, "蓝光"),
+    (5, "原画"),
+];
+
+pub(super) fn gear_name(gear: u32) -> String {
+    GEARS
+        .iter()
+        .find(|(g, _)| *g == gear)
+        .map_or_else(|| format!("档位 {}", gear), |(_, name)| name.to_string())
+}
@@ -1,30 +1,43 @@
+mod constants;
+mod room_resolver;
+
 use std::collections::HashMap;
 
 use regex::Regex;
-use serde_json::Value;
+use serde_json::{json, Value};
 
-use crate::error::{LsarResult, MissKeyFieldError};
+use crate::config::Config;
+use crate::error::{LsarResult, MissKeyFieldError, RoomStateError};
 use crate::network::http::Client;
-use crate::parsers::{ParsedResult, Parser};
+use crate::parsers::{Link, ParsedResult, Parser, Quality};
 use crate::platform::Platform;
 use crate::utils::{decode_uri_component, now};
 
+use self::constants::{
+    gear_name, APP, BID, CLIENT_VERSION, DEFAULT_GEAR, GEARS, HEAD_CLIENT_TYPE, WEB_CLIENT_TYPE,
+};
+use self::room_resolver::{resolve_channel, Channel};
+
 pub struct YYParser {
-    room_id: u64,
+    channel: Channel,
+    /// 请求的档位
+    gear: u32,
     http_client: Client,
 }
 
 impl YYParser {
-    fn new(room_id: u64) -> Self {
+    fn new(channel: Channel, gear: u32) -> Self {
         Self {
-            room_id,
+            channel,
+            gear,
             http_client: Client::new(),
         }
     }
 
     async fn get_room_info(&self) -> LsarResult<ParsedResult> {
-        debug!(room_id = self.room_id, "Parsing YY room info");
-        let url = format!("https://www.yy.com/{}", self.room_id);
+        let Channel { sid, ssid } = self.channel;
+        debug!(sid, ssid, "Parsing YY room info");
+        let url = format!("https://www.yy.com/{}/{}", sid, ssid);
         let resp = self.http_client.get_text(&url).await?;
 
         let regex = Regex::new(
@@ -62,50 +75,241 @@ impl YYParser {
             platform: Platform::YY,
             title,
             anchor: nickname,
-            room_id: self.room_id,
+            room_id: self.channel.room_id(),
             category,
             links: Vec::new(),
+            qualities: Vec::new(),
+            guest: false,
+            scheduled_start: None,
+            keep_alive_session: None,
+        })
+    }
+
+    fn request_body(&self, uid: u64, sequence: u128) -> Value {
+        json!({
+            "head": {
+                "seq": sequence,
+                "appidstr": "0",
+                "bidstr": BID,
+                "cidstr": self.channel.ssid.to_string(),
+                "sidstr": self.channel.sid.to_string(),
+                "uid64": uid,
+                "client_type": HEAD_CLIENT_TYPE,
+                "client_ver": CLIENT_VERSION,
+                "stream_sys_ver": 1,
+                "app": APP,
+                "playersdk_ver": CLIENT_VERSION,
+                "thundersdk_ver": "0",
+                "streamsdk_ver": CLIENT_VERSION
+            },
+            "client_attribute": {
+                "client": "web",
+                "model": "web0",
+                "cpu": "",
+                "graphics_card": "",
+                "os": "chrome",
+                "osversion": "",
+                "vsdk_version": "",
+                "app_identify": "",
+                "app_version": "",
+                "business": "",
+                "width": "1920",
+                "height": "1080",
+                "scale": "",
+                "client_type": WEB_CLIENT_TYPE,
+                "h265": 0
+            },
+            "avp_parameter": {
+                "version": 1,
+                "client_type": WEB_CLIENT_TYPE,
+                "service_type": 0,
+                "imsi": 0,
+                "send_time": sequence / 1000,
+                "line_seq": -1,
+                "gear": self.gear,
+                "ssl": 1,
+                "stream_format": 0
+            }
         })
     }
 
-    async fn get_streams(&self) -> LsarResult<Vec<String>> {
-        let now = now()?;
-        let sequence = now.as_millis();
-        let url = format!("https://stream-manager.yy.com/v3/channel/streams?uid=3071000363&cid={}&sid={}&appid=0&sequence={}&encode=json", self.room_id, self.room_id, sequence);
-
-        let body = format!(
-            r#"{{"head":{{"seq":{},"appidstr":"0","bidstr":"120","cidstr":"{}","sidstr":"{}","uid64":0,"client_type":108,"client_ver":"5.19.4","stream_sys_ver":1,"app":"yylive_web","playersdk_ver":"5.19.4","thundersdk_ver":"0","streamsdk_ver":"5.19.4"}},"client_attribute":{{"client":"web","model":"web0","cpu":"","graphics_card":"","os":"chrome","osversion":"141.0.0.0","vsdk_version":"","app_identify":"","app_version":"","business":"","width":"1536","height":"960","scale":"","client_type":8,"h265":0}},"avp_parameter":{{"version":1,"client_type":8,"service_type":0,"imsi":0,"send_time":{},"line_seq":-1,"gear":2,"ssl":1,"stream_format":0}}}}"#,
-            sequence,
-            self.room_id,
-            self.room_id,
-            sequence / 1000
+    async fn get_streams(&self) -> LsarResult<(Vec<Link>, Vec<Quality>)> {
+        let uid = Config::yy_uid().await?;
+        let sequence = now()?.as_millis();
+        let url = format!(
+            "https://stream-manager.yy.com/v3/channel/streams?uid={}&cid={}&sid={}&appid=0&sequence={}&encode=json",
+            uid, self.channel.ssid, self.channel.sid, sequence
         );
 
+        let body = self.request_body(uid, sequence).to_string();
         let resp: Value = self.http_client.post_plain(&url, &body).await?;
-        let streams: HashMap<String, Value> =
-            serde_json::from_value(resp["avp_info_res"]["stream_line_addr"].clone())?;
 
-        let urls = streams
-            .values()
-            .map(|value| value["cdn_info"]["url"].as_str().unwrap().to_string())
-            .collect();
+        Ok((parse_lines(&resp)?, parse_gears(&resp)))
+    }
+}
+
+/// 每条线路一个链接，标注档位和线路名
+///
+/// 档位取自响应中 `channel_stream_info.streams` 对应流的 `gear`，
+/// 请求的档位不可用时接口会返回其他档位
+fn parse_lines(resp: &Value) -> LsarResult<Vec<Link>> {
+    let avp_info = &resp["avp_info_res"];
+    let lines = avp_info["stream_line_addr"]
+        .as_object()
+        .ok_or(RoomStateError::Offline)?;
+
+    // 线路名在 stream_line_list 中，按 line_seq 对应
+    let line_names: HashMap<i64, &str> = avp_info["stream_line_list"]
+        .as_object()
+        .into_iter()
+        .flat_map(|list| list.values())
+        .filter_map(|stream| stream["line_infos"].as_array())
+        .flatten()
+        .filter_map(|line| {
+            Some((
+                line["line_seq"].as_i64()?,
+                line["line_print_name"].as_str()?,
+            ))
+        })
+        .collect();
+
+    let streams: Vec<&Value> = resp["channel_stream_info"]["streams"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .collect();
+    let gear_of = |stream_key: &str| -> Option<u64> {
+        let gear = |stream: &&Value| stream["gear"].as_u64();
+        streams
+            .iter()
+            .find(|stream| stream["stream_key"] == stream_key)
+            .and_then(gear)
+            // 只有一路流时线路的键可能与 stream_key 不同
+            .or_else(|| match streams.as_slice() {
+                [stream] => gear(stream),
+                _ => None,
+            })
+    };
+
+    let links = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (stream_key, line))| {
+            let url = line["cdn_info"]["url"].as_str()?;
+            let name = line["line_print_name"]
+                .as_str()
+                .or_else(|| {
+                    line["line_seq"]
+                        .as_i64()
+                        .and_then(|seq| line_names.get(&seq).copied())
+                })
+                .map_or_else(|| format!("线路 {}", index + 1), str::to_string);
+
+            let link = Link::new(url).cdn(name).format("FLV");
+            Some(match gear_of(stream_key) {
+                Some(gear) => link.quality(gear_name(gear as u32)),
+                None => link,
+            })
+        })
+        .collect();
+
+    Ok(links)
+}
+
+/// 可选档位，从 `channel_stream_info.streams` 中取，没有时返回全部已知档位
+fn parse_gears(resp: &Value) -> Vec<Quality> {
+    let mut gears: Vec<u32> = resp["channel_stream_info"]["streams"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|stream| stream["gear"].as_u64())
+        .map(|gear| gear as u32)
+        .collect();
+    gears.sort_unstable_by(|a, b| b.cmp(a));
+    gears.dedup();
 
-        Ok(urls)
+    if gears.is_empty() {
+        gears = GEARS.iter().rev().map(|(gear, _)| *gear).collect();
     }
+
+    gears
+        .into_iter()
+        .map(|gear| Quality::new(gear as i64, gear_name(gear)))
+        .collect()
 }
 
 impl Parser for YYParser {
     async fn parse(&mut self) -> LsarResult<ParsedResult> {
         let mut result = self.get_room_info().await?;
-        let streams = self.get_streams().await?;
-        result.links = streams;
+        let (links, qualities) = self.get_streams().await?;
+        result.links = links;
+        result.qualities = qualities;
 
         Ok(result)
     }
 }
 
+/// `room_id` 和 `url` 二选一，子频道只能通过 `url` 指定
 #[tauri::command]
-pub async fn parse_yy(room_id: u64) -> LsarResult<ParsedResult> {
-    let mut yy = YYParser::new(room_id);
+pub async fn parse_yy(
+    room_id: Option<u64>,
+    url: String,
+    gear: Option<u32>,
+) -> LsarResult<ParsedResult> {
+    let channel = match room_id {
+        Some(room_id) => Channel::new(room_id),
+        None => resolve_channel(&url)?,
+    };
+    let mut yy = YYParser::new(channel, gear.unwrap_or(DEFAULT_GEAR));
     yy.parse().await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> Value {
+        json!({
+            "avp_info_res": {
+                "stream_line_addr": {
+                    "yy_2": {"cdn_info": {"url": "https://tx.flv.huya.example/a.flv"}, "line_seq": 1},
+                    "yy_4": {"cdn_info": {"url": "https://al.flv.example/a.flv"}, "line_seq": 2}
+                },
+                "stream_line_list": {
+                    "yy_2": {"line_infos": [{"line_seq": 1, "line_print_name": "线路一"}]}
+                }
+            },
+            "channel_stream_info": {
+                "streams": [{"stream_key": "yy_2", "gear": 2}, {"stream_key": "yy_4", "gear": 4}]
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let links = parse_lines(&response()).unwrap();
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].url, "https://tx.flv.huya.example/a.flv");
+        assert_eq!(links[0].cdn.as_deref(), Some("线路一"));
+        assert_eq!(links[0].quality.as_deref(), Some("高清"));
+        assert_eq!(links[1].cdn.as_deref(), Some("线路 2"));
+        assert_eq!(links[1].quality.as_deref(), Some("蓝光"));
+        assert!(parse_lines(&json!({})).is_err());
+
+        // 只有一路流时使用其档位
+        let mut resp = response();
+        resp["channel_stream_info"]["streams"] = json!([{"stream_key": "yy_5", "gear": 5}]);
+        let links = parse_lines(&resp).unwrap();
+        assert!(links.iter().all(|l| l.quality.as_deref() == Some("原画")));
+    }
+
+    #[test]
+    fn test_parse_gears() {
+        let gears: Vec<i64> = parse_gears(&response()).iter().map(|q| q.value).collect();
+        assert_eq!(gears, vec![4, 2]);
+
+        let gears: Vec<i64> = parse_gears(&json!({})).iter().map(|q| q.value).collect();
+        assert_eq!(gears, vec![5, 4, 3, 2, 1]);
+    }
+}
@@ -0,0 +1,130 @@
+use url::Url;
+
+use crate::error::{LsarError, LsarResult, MissKeyFieldError};
+
+/// 手机端和分享页面路径中位于频道号之前的前缀
+const PATH_PREFIXES: [&str; 3] = ["mobileweb", "share", "room"];
+
+/// YY 频道，`sid` 为顶级频道号，`ssid` 为子频道号，没有子频道时两者相同
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Channel {
+    pub sid: u64,
+    pub ssid: u64,
+}
+
+impl Channel {
+    pub fn new(sid: u64) -> Self {
+        Self { sid, ssid: sid }
+    }
+
+    /// 保存到历史记录的房间号，子频道与顶级频道不同时为 `<sid>/<ssid>`，
+    /// 与链接 `yy.com/<sid>/<ssid>` 的路径一致，可以重新解析
+    pub fn room_id(&self) -> String {
+        if self.sid == self.ssid {
+            self.sid.to_string()
+        } else {
+            format!("{}/{}", self.sid, self.ssid)
+        }
+    }
+}
+
+/// 从用户输入的 YY 链接中解析频道号
+///
+/// 支持的形式：
+/// - `yy.com/<sid>` 和 `yy.com/<sid>/<ssid>`
+/// - `wap.yy.com/mobileweb/<sid>/<ssid>` 等手机端页面
+/// - 带有 `sid`、`ssid` 参数的分享链接
+pub(super) fn resolve_channel(input: &str) -> LsarResult<Channel> {
+    let url = Url::parse(input)?;
+    let host = url.host_str().unwrap_or_default();
+
+    if host != "yy.com" && !host.ends_with(".yy.com") {
+        error!("Unsupported YY URL: {}", url);
+        return Err(LsarError::Other(format!("不支持的 YY 链接: {}", url)));
+    }
+
+    let query_param = |names: &[&str]| {
+        url.query_pairs()
+            .find(|(k, _)| names.contains(&k.as_ref()))
+            .and_then(|(_, v)| v.parse::<u64>().ok())
+    };
+    if let Some(sid) = query_param(&["sid", "topSid"]) {
+        let ssid = query_param(&["ssid", "subSid"]).unwrap_or(sid);
+        debug!(sid, ssid, "Found channel in share link");
+        return Ok(Channel { sid, ssid });
+    }
+
+    let mut ids = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty() && !PATH_PREFIXES.contains(s))
+        .map_while(|s| s.parse::<u64>().ok());
+
+    let sid = ids.next().ok_or(MissKeyFieldError::RoomId)?;
+    let ssid = ids.next().unwrap_or(sid);
+    debug!(sid, ssid, "Found channel in URL path");
+
+    Ok(Channel { sid, ssid })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_desktop_url() {
+        assert_eq!(
+            resolve_channel("https://www.yy.com/22490906").unwrap(),
+            Channel::new(22490906)
+        );
+        assert_eq!(
+            resolve_channel("https://www.yy.com/54880976/2793498934?tempId=16777217").unwrap(),
+            Channel {
+                sid: 54880976,
+                ssid: 2793498934
+            }
+        );
+    }
+
+    #[test]
+    fn test_room_id() {
+        assert_eq!(Channel::new(22490906).room_id(), "22490906");
+
+        let channel = resolve_channel("https://www.yy.com/54880976/2793498934").unwrap();
+        assert_eq!(channel.room_id(), "54880976/2793498934");
+        assert_eq!(
+            resolve_channel(&format!("https://www.yy.com/{}", channel.room_id())).unwrap(),
+            channel
+        );
+    }
+
+    #[test]
+    fn test_resolve_mobile_and_share_url() {
+        assert_eq!(
+            resolve_channel("https://wap.yy.com/mobileweb/54880976/2793498934").unwrap(),
+            Channel {
+                sid: 54880976,
+                ssid: 2793498934
+            }
+        );
+        assert_eq!(
+            resolve_channel("https://www.yy.com/share/i/?sid=54880976&ssid=2793498934").unwrap(),
+            Channel {
+                sid: 54880976,
+                ssid: 2793498934
+            }
+        );
+        assert_eq!(
+            resolve_channel("https://wap.yy.com/mobileweb/22490906").unwrap(),
+            Channel::new(22490906)
+        );
+    }
+
+    #[test]
+    fn test_resolve_invalid_url() {
+        assert!(resolve_channel("https://www.huya.com/22490906").is_err());
+        assert!(resolve_channel("https://www.yy.com/").is_err());
+        assert!(resolve_channel("https://www.yy.com/abc").is_err());
+    }
+}
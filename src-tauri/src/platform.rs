@@ -9,6 +9,16 @@ pub(crate) enum Platform {
     Bilibili,
     Bigo,
     YY,
+    Kuaishou,
+    Twitch,
+    Youtube,
+    CC,
+    Acfun,
+    Soop,
+    Chzzk,
+    Tiktok,
+    Niconico,
+    Twitcasting,
 }
 
 impl Platform {
@@ -20,6 +30,16 @@ impl Platform {
             Platform::Bilibili => 3,
             Platform::Bigo => 4,
             Platform::YY => 5,
+            Platform::Kuaishou => 6,
+            Platform::Twitch => 7,
+            Platform::Youtube => 8,
+            Platform::CC => 9,
+            Platform::Acfun => 10,
+            Platform::Soop => 11,
+            Platform::Chzzk => 12,
+            Platform::Tiktok => 13,
+            Platform::Niconico => 14,
+            Platform::Twitcasting => 15,
         }
     }
 
@@ -39,6 +59,16 @@ impl TryFrom<i64> for Platform {
             3 => Ok(Platform::Bilibili),
             4 => Ok(Platform::Bigo),
             5 => Ok(Platform::YY),
+            6 => Ok(Platform::Kuaishou),
+            7 => Ok(Platform::Twitch),
+            8 => Ok(Platform::Youtube),
+            9 => Ok(Platform::CC),
+            10 => Ok(Platform::Acfun),
+            11 => Ok(Platform::Soop),
+            12 => Ok(Platform::Chzzk),
+            13 => Ok(Platform::Tiktok),
+            14 => Ok(Platform::Niconico),
+            15 => Ok(Platform::Twitcasting),
             _ => Err("Invalid platform value"),
         }
     }
@@ -53,6 +83,16 @@ impl From<&Platform> for &'static str {
             Platform::Bilibili => "bilibili",
             Platform::Bigo => "bigo",
             Platform::YY => "yy",
+            Platform::Kuaishou => "kuaishou",
+            Platform::Twitch => "twitch",
+            Platform::Youtube => "youtube",
+            Platform::CC => "cc",
+            Platform::Acfun => "acfun",
+            Platform::Soop => "soop",
+            Platform::Chzzk => "chzzk",
+            Platform::Tiktok => "tiktok",
+            Platform::Niconico => "niconico",
+            Platform::Twitcasting => "twitcasting",
         }
     }
 }
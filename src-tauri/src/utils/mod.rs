@@ -3,5 +3,5 @@ mod time;
 mod url_decode;
 
 pub use self::md5::md5;
-pub use self::time::now;
+pub use self::time::{now, now_millis};
 pub use self::url_decode::decode_uri_component;
@@ -8,6 +8,6 @@ pub fn now() -> LsarResult<Duration> {
         .map_err(LsarError::SystemTime)
 }
 
-// pub fn now_millis() -> LsarResult<u64> {
-//     Ok(now()?.as_millis() as u64)
-// }
+pub fn now_millis() -> LsarResult<u64> {
+    Ok(now()?.as_millis() as u64)
+}
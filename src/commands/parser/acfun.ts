@@ -0,0 +1,8 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export const parseAcfun = async (roomID: number) => {
+  const result = await invoke<ParsedResult>("parse_acfun", {
+    roomId: roomID,
+  });
+  return result;
+};
@@ -1,8 +1,12 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export const parseBigo = async (roomId: number) => {
+/**
+ * @param url 房间号为 0 时使用，支持 `bigo.tv/<语言>/<用户名>` 形式的链接
+ */
+export const parseBigo = async (roomId: number, url: string) => {
   const result = await invoke<ParsedResult>("parse_bigo", {
-    roomId,
+    roomId: roomId || null,
+    url,
   });
   return result;
 };
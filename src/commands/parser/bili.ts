@@ -1,14 +1,58 @@
 import { invoke } from "@tauri-apps/api/core";
 
+export interface BilibiliPlayOptions {
+  qn?: number;
+  protocol?: "http_stream" | "http_hls";
+  format?: "flv" | "ts" | "fmp4";
+  codec?: "avc" | "hevc";
+}
+
 export const parseBilibili = async (
   roomID: number,
   cookie: string,
   url: string,
+  options: BilibiliPlayOptions = {},
+  password?: string,
 ) => {
   const result = await invoke<ParsedResult>("parse_bilibili", {
     roomId: roomID,
     cookie: cookie,
     url: url || null,
+    options,
+    password: password || null,
   });
   return result;
 };
+
+interface BilibiliQrcode {
+  url: string;
+  qrcode_key: string;
+}
+
+type BilibiliQrcodeStatus = "waiting" | "scanned" | "expired" | "success";
+
+export const generateBilibiliQrcode = async () =>
+  invoke<BilibiliQrcode>("generate_bilibili_qrcode");
+
+// 登录成功后 cookie 已由后端写入配置文件，需重新读取配置
+export const pollBilibiliQrcode = async (qrcodeKey: string) =>
+  invoke<BilibiliQrcodeStatus>("poll_bilibili_qrcode", { qrcodeKey });
+
+export interface BilibiliFollowedRoom {
+  room_id: number;
+  uid: number;
+  anchor: string;
+  title: string;
+  category: string;
+  cover: string;
+}
+
+// 返回正在直播的关注主播，addToHistory 为 true 时同时导入到历史记录
+export const getBilibiliFollowedLiveRooms = async (
+  cookie: string,
+  addToHistory = false,
+) =>
+  invoke<BilibiliFollowedRoom[]>("get_bilibili_followed_live_rooms", {
+    cookie,
+    addToHistory,
+  });
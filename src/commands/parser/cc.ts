@@ -0,0 +1,8 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export const parseCC = async (roomID: number) => {
+  const result = await invoke<ParsedResult>("parse_cc", {
+    roomId: roomID,
+  });
+  return result;
+};
@@ -0,0 +1,11 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * 年龄限制的直播使用配置文件中的 Naver cookie
+ *
+ * @param url `chzzk.naver.com/live/<频道 ID>` 链接
+ */
+export const parseChzzk = async (url: string) => {
+  const result = await invoke<ParsedResult>("parse_chzzk", { url });
+  return result;
+};
@@ -1,8 +1,9 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export const parseDouyin = async (roomID: number) => {
+export const parseDouyin = async (roomID: number, url: string) => {
   const result = await invoke<ParsedResult>("parse_douyin", {
     roomId: roomID,
+    url: url || null,
   });
   return result;
 };
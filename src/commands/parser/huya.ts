@@ -1,9 +1,32 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export const parseHuya = async (roomID: number, url: string) => {
+export const parseHuya = async (
+  roomID: number,
+  url: string,
+  ratio?: number,
+  cdn?: string,
+) => {
   const result = await invoke<ParsedResult>("parse_huya", {
     roomId: roomID || null,
     url: url,
+    ratio: ratio ?? null,
+    cdn: cdn ?? null,
   });
   return result;
 };
+
+interface RefreshedLink {
+  url: string;
+  expires_in: number;
+}
+
+interface LinkLifetime {
+  expires_in: number;
+  should_refresh: boolean;
+}
+
+export const refreshHuyaLink = async (roomID: number, url: string) =>
+  invoke<RefreshedLink>("refresh_huya_link", { roomId: roomID, url });
+
+export const getHuyaLinkLifetime = async (url: string) =>
+  invoke<LinkLifetime>("get_huya_link_lifetime", { url });
@@ -1,6 +1,21 @@
+export { parseAcfun } from "./acfun";
 export { parseBigo } from "./bigo";
-export { parseBilibili } from "./bili";
+export {
+  generateBilibiliQrcode,
+  getBilibiliFollowedLiveRooms,
+  parseBilibili,
+  pollBilibiliQrcode,
+} from "./bili";
+export { parseCC } from "./cc";
+export { parseChzzk } from "./chzzk";
 export { parseDouyin } from "./douyin";
 export { parseDouyu } from "./douyu";
-export { parseHuya } from "./huya";
+export { getHuyaLinkLifetime, parseHuya, refreshHuyaLink } from "./huya";
+export { parseKuaishou } from "./kuaishou";
+export { parseNiconico, stopNiconicoKeepAlive } from "./niconico";
+export { parseSoop } from "./soop";
+export { parseTiktok } from "./tiktok";
+export { parseTwitcasting } from "./twitcasting";
+export { parseTwitch } from "./twitch";
+export { parseYoutube } from "./youtube";
 export { parseYY } from "./yy";
@@ -0,0 +1,13 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * @param roomID 纯数字的主播 ID，为 0 时使用 url
+ * @param url 直播间链接或 `v.kuaishou.com` 分享短链
+ */
+export const parseKuaishou = async (roomID: number, url: string) => {
+  const result = await invoke<ParsedResult>("parse_kuaishou", {
+    roomId: roomID || null,
+    url,
+  });
+  return result;
+};
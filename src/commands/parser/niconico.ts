@@ -0,0 +1,19 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * 解析成功后后端会保持观看会话，直播流链接在会话结束前有效
+ *
+ * @param url `live.nicovideo.jp/watch/<节目 ID>` 链接，也可以是社区或频道 ID
+ */
+export const parseNiconico = async (url: string) => {
+  const result = await invoke<ParsedResult>("parse_niconico", { url });
+  return result;
+};
+
+/**
+ * 结束观看会话，之后直播流链接会失效
+ *
+ * @param session 解析结果中的 `keepAliveSession`，已被替换的会话会被忽略
+ */
+export const stopNiconicoKeepAlive = async (session: number) =>
+  invoke<void>("stop_niconico_keep_alive", { session });
@@ -0,0 +1,19 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * @param url `play.sooplive.co.kr/<bj>/<bno>` 或 `play.afreecatv.com` 链接
+ * @param quality 画质值，来自解析结果的 `qualities`，默认最高画质；界面中暂不提供画质选择
+ * @param password 加密直播的密码
+ */
+export const parseSoop = async (
+  url: string,
+  quality?: number,
+  password?: string,
+) => {
+  const result = await invoke<ParsedResult>("parse_soop", {
+    url,
+    quality: quality ?? null,
+    password: password || null,
+  });
+  return result;
+};
@@ -0,0 +1,9 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * @param url `tiktok.com/@<用户名>/live` 或主页链接
+ */
+export const parseTiktok = async (url: string) => {
+  const result = await invoke<ParsedResult>("parse_tiktok", { url });
+  return result;
+};
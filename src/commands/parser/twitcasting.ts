@@ -0,0 +1,13 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * @param url `twitcasting.tv/<用户 ID>` 链接
+ * @param password 加密直播的密码
+ */
+export const parseTwitcasting = async (url: string, password?: string) => {
+  const result = await invoke<ParsedResult>("parse_twitcasting", {
+    url,
+    password: password || null,
+  });
+  return result;
+};
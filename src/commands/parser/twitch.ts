@@ -0,0 +1,9 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * @param url 频道名或 `twitch.tv/<频道名>` 链接
+ */
+export const parseTwitch = async (url: string) => {
+  const result = await invoke<ParsedResult>("parse_twitch", { url });
+  return result;
+};
@@ -0,0 +1,9 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * @param url 频道（`@handle`）、直播（`/watch?v=`、`/live/`）或 `youtu.be` 链接
+ */
+export const parseYoutube = async (url: string) => {
+  const result = await invoke<ParsedResult>("parse_youtube", { url });
+  return result;
+};
@@ -1,8 +1,14 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export const parseYY = async (roomID: number) => {
+/**
+ * @param url 房间号为 0 时使用，支持子频道、手机端和分享链接
+ * @param gear 档位，默认 2（高清）
+ */
+export const parseYY = async (roomID: number, url: string, gear?: number) => {
   const result = await invoke<ParsedResult>("parse_yy", {
-    roomId: roomID,
+    roomId: roomID || null,
+    url,
+    gear,
   });
   return result;
 };
@@ -39,7 +39,12 @@ const HistoryItem = (props: HistoryItemProps) => {
   };
 
   const handleParse = async () => {
-    onParse(props.platform, props.room_id, props.index);
+    const platform = platforms[props.platform];
+    const input =
+      "numericRoomID" in platform
+        ? Number(props.room_id)
+        : platform.roomBaseURL + props.room_id;
+    onParse(props.platform, input, props.index);
   };
 
   return (
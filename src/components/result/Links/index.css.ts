@@ -29,6 +29,12 @@ export const itemLinkContainer = style({
   textOverflow: "ellipsis",
 });
 
+export const itemLabel = style({
+  marginRight: vars.spacingHorizontalS,
+  color: vars.colorNeutralForeground3,
+  fontSize: vars.fontSizeBase200,
+});
+
 export const itemLink = style({
   maxWidth: "100%",
 });
@@ -1,4 +1,4 @@
-import { For } from "solid-js";
+import { For, Show } from "solid-js";
 import { AiFillCopy, AiFillPlayCircle } from "solid-icons/ai";
 
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
@@ -30,7 +30,7 @@ const Links = (props: LinksProps) => {
   };
 
   const onPlay = async (index: number) => {
-    await play(props.links[index]);
+    await play(props.links[index].url);
 
     // 解析出来的链接只能访问一次，访问后即删除
     removeLink(index);
@@ -63,7 +63,7 @@ const Links = (props: LinksProps) => {
           <LinkItem
             link={link}
             onPlay={() => onPlay(index())}
-            onCopy={() => onCopy(link)}
+            onCopy={() => onCopy(link.url)}
           />
         )}
       </For>
@@ -72,17 +72,26 @@ const Links = (props: LinksProps) => {
 };
 
 interface LinkItemProps {
-  link: string;
+  link: Link;
   onPlay: () => void;
   onCopy: () => void;
 }
 
 const LinkItem = (props: LinkItemProps) => {
+  // 画质 · 线路 · 格式 · 编码，未提供的项不显示
+  const label = () =>
+    [props.link.quality, props.link.cdn, props.link.format, props.link.codec]
+      .filter(Boolean)
+      .join(" · ");
+
   return (
     <div class={styles.item}>
       <div class={styles.itemLinkContainer}>
+        <Show when={label()}>
+          <span class={styles.itemLabel}>{label()}</span>
+        </Show>
         <LazyLink class={styles.itemLink} onClick={() => props.onPlay()}>
-          {props.link}
+          {props.link.url}
         </LazyLink>
       </div>
 
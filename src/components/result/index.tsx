@@ -81,6 +81,38 @@ const Result = () => {
         <div class={styles.content}>
           <Links {...parsedResult()!} />
 
+          <Show when={parsedResult()?.scheduledStart}>
+            {(start) => (
+              <MessageBar>
+                <LazyText>
+                  直播尚未开始，预计开始时间：
+                  {new Date(start() * 1000).toLocaleString()}
+                </LazyText>
+              </MessageBar>
+            )}
+          </Show>
+
+          <Show
+            when={
+              parsedResult()?.platform === "niconico" &&
+              parsedResult()?.links.length
+            }
+          >
+            <MessageBar>
+              <LazyText>
+                直播流链接仅在保留此解析结果期间有效，重新解析或清空结果后会失效
+              </LazyText>
+            </MessageBar>
+          </Show>
+
+          <Show when={parsedResult()?.guest}>
+            <MessageBar>
+              <LazyText>
+                当前以游客身份解析，更高的画质需要在设置中登录
+              </LazyText>
+            </MessageBar>
+          </Show>
+
           <MessageBar>
             <LazyText>
               点击播放按钮后请等待1~3秒用于加载，若播放失败，可尝试播放其他链接或者重新解析
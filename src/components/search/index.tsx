@@ -13,10 +13,16 @@ import * as styles from "./index.css";
 
 const Search = () => {
   const { setParsedResult } = useParsedResultContext();
-  const { isHistoryItemParsing, isSearchParsing, onParse } =
-    useParsingContext();
+  const {
+    isHistoryItemParsing,
+    isSearchParsing,
+    onParse,
+    passwordRequest,
+    setPasswordRequest,
+  } = useParsingContext();
 
   const [input, setInput] = createSignal<string>();
+  const [password, setPassword] = createSignal("");
   const [currentPlatform, setCurrentPlatform] = createSignal<Platform | null>(
     null,
   );
@@ -53,6 +59,20 @@ const Search = () => {
     if (input()?.trim() && e.key === "Enter") handleParse();
   };
 
+  // 使用输入的密码重新解析上一次需要密码的直播间
+  const handleParseWithPassword = () => {
+    const request = passwordRequest();
+    if (!request || !password()) return;
+
+    onParse(request.platform, request.value, request.index, password());
+    setPassword("");
+  };
+
+  const handlePasswordEnterDown = (e: KeyboardEvent) => {
+    if (e.key === "Enter") handleParseWithPassword();
+    else if (e.key === "Escape") setPasswordRequest(null);
+  };
+
   return (
     <>
       <div class={styles.badges}>
@@ -108,6 +128,18 @@ const Search = () => {
           />
         }
       />
+
+      <Show when={passwordRequest()}>
+        <LazyInput
+          appearance="underline"
+          type="password"
+          placeholder="输入房间密码后按回车重新解析"
+          value={password()}
+          onInput={(v: string) => setPassword(v)}
+          onKeyDown={handlePasswordEnterDown}
+          disabled={isHistoryItemParsing() || isSearchParsing()}
+        />
+      </Show>
     </>
   );
 };
@@ -0,0 +1,21 @@
+import { LazyTextArea } from "~/lazy";
+import SettingItem from "../SettingItem";
+
+interface ChzzkCookieProps {
+  cookie?: string;
+  onChange?: (cookie: string) => void;
+}
+
+const ChzzkCookie = (props: ChzzkCookieProps) => {
+  return (
+    <SettingItem
+      label="CHZZK Cookie"
+      orientation="vertical"
+      tips="只有观看年龄限制的 CHZZK 直播时需要，填写 Naver 账号的 NID_AUT 和 NID_SES，如：NID_AUT=xxx; NID_SES=xxx"
+    >
+      <LazyTextArea rows={3} value={props.cookie} onChange={props.onChange} />
+    </SettingItem>
+  );
+};
+
+export default ChzzkCookie;
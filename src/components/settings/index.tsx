@@ -9,6 +9,7 @@ import { Drawer } from "../drawer/Drawer";
 import DarkMode from "./components/DarkMode";
 import PlayerPath from "./components/PlayerPath";
 import BiliCookie from "./components/BiliCookie";
+import ChzzkCookie from "./components/ChzzkCookie";
 
 import { useToast } from "fluent-solid";
 import { useConfigContext } from "~/contexts/ConfigContext";
@@ -127,6 +128,19 @@ const Settings = () => {
           }
         />
 
+        <ChzzkCookie
+          cookie={lsarConfig()?.platform.chzzk?.cookie}
+          onChange={(cookie) =>
+            setLsarConfig(
+              (prev) =>
+                prev && {
+                  ...prev,
+                  platform: { ...prev.platform, chzzk: { cookie } },
+                },
+            )
+          }
+        />
+
         <div class={styles.buttons}>
           <LazyButton onClick={onCancel} disabled={!lsarConfig()?.player.path}>
             取消
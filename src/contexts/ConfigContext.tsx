@@ -1,10 +1,13 @@
 import {
   createContext,
   createResource,
+  onCleanup,
   type ParentProps,
   type Resource,
   useContext,
 } from "solid-js";
+import { listen } from "@tauri-apps/api/event";
+import { useToast } from "fluent-solid";
 import { readConfigFile } from "~/command";
 
 interface ConfigContextValue {
@@ -15,8 +18,21 @@ interface ConfigContextValue {
 const ConfigContext = createContext<ConfigContextValue>();
 
 export const ConfigContextProvider = (props: ParentProps) => {
+  const toast = useToast();
   const [config, { refetch: refetchConfig }] = createResource(readConfigFile);
 
+  // 后端刷新 B 站 cookie 后已写入配置文件
+  const unlistenRefreshed = listen("bilibili-cookie-refreshed", () =>
+    refetchConfig(),
+  );
+  const unlistenWarning = listen<string>("bilibili-cookie-warning", (e) =>
+    toast.warning(e.payload, { position: "bottom-right" }),
+  );
+  onCleanup(() => {
+    unlistenRefreshed.then((f) => f());
+    unlistenWarning.then((f) => f());
+  });
+
   return (
     <ConfigContext.Provider
       value={{
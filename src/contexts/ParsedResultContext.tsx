@@ -1,12 +1,16 @@
 import {
   type Accessor,
   createContext,
+  createEffect,
   createSignal,
+  on,
   type ParentProps,
   type Setter,
   useContext,
 } from "solid-js";
 
+import { stopNiconicoKeepAlive } from "~/commands/parser";
+
 interface ParsedResultContextValue {
   parsedResult: Accessor<ParsedResult | undefined>;
   setParsedResult: Setter<ParsedResult | undefined>;
@@ -17,6 +21,17 @@ const ParsedResultContext = createContext<ParsedResultContextValue>();
 export const ParsedResultContextProvider = (props: ParentProps) => {
   const [parsedResult, setParsedResult] = createSignal<ParsedResult>();
 
+  // Niconico 的直播流需要后端保持观看会话，解析结果被清空或换成其他会话时结束会话，
+  // 删除链接等只修改当前结果的操作会保留会话
+  createEffect(
+    on(parsedResult, (current, previous) => {
+      const session = previous?.keepAliveSession;
+      if (session !== undefined && current?.keepAliveSession !== session) {
+        stopNiconicoKeepAlive(session);
+      }
+    }),
+  );
+
   return (
     <ParsedResultContext.Provider value={{ parsedResult, setParsedResult }}>
       {props.children}
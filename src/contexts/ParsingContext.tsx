@@ -11,11 +11,17 @@ import {
 import { useToast } from "fluent-solid";
 
 import { isValidNumberOrHttpsUrl } from "~/parser/validators";
-import { parse } from "~/parser";
+import { isPasswordError, parse, platforms } from "~/parser";
 import { useConfigContext } from "./ConfigContext";
-import { useSettingsContext } from "./SettingsContext";
 import { useParsedResultContext } from "./ParsedResultContext";
 
+// 解析加密直播间时需要用户输入密码后重新解析
+interface PasswordRequest {
+  platform: Platform;
+  value: string | number;
+  index: number;
+}
+
 interface ParsingContextValue {
   parsingHistoryItemIndex: Accessor<number | null>;
   setParsingHistoryItemIndex: Setter<number | null>;
@@ -27,7 +33,10 @@ interface ParsingContextValue {
     platform: Platform,
     value: string | number,
     index?: number,
+    password?: string,
   ) => Promise<void>;
+  passwordRequest: Accessor<PasswordRequest | null>;
+  setPasswordRequest: Setter<PasswordRequest | null>;
 }
 
 const ParsingContext = createContext<ParsingContextValue>();
@@ -35,13 +44,14 @@ const ParsingContext = createContext<ParsingContextValue>();
 export const ParsingContextProvider = (props: ParentProps) => {
   const toast = useToast();
   const { config } = useConfigContext();
-  const { setShowSettings: setShowBilibiliCookieEditor } = useSettingsContext();
   const { setParsedResult } = useParsedResultContext();
 
   const [parsingHistoryItemIndex, setParsingHistoryItemIndex] = createSignal<
     number | null
   >(null);
   const [parsing, setParsing] = createSignal(false);
+  const [passwordRequest, setPasswordRequest] =
+    createSignal<PasswordRequest | null>(null);
 
   const isHistoryItemParsing = createMemo(() => {
     const index = parsingHistoryItemIndex();
@@ -56,6 +66,7 @@ export const ParsingContextProvider = (props: ParentProps) => {
     platform: Platform,
     value: string | number,
     index: number = -1,
+    password?: string,
   ) => {
     if (!value) return;
 
@@ -67,18 +78,20 @@ export const ParsingContextProvider = (props: ParentProps) => {
     }
 
     setParsedResult(); // 清空解析结果
+    setPasswordRequest(null);
     setParsingHistoryItemIndex(index);
     setParsing(true);
 
-    const result = await parse(
-      platform,
-      parsedInput,
-      config()!,
-      setShowBilibiliCookieEditor,
-    );
+    const result = await parse(platform, parsedInput, config()!, password);
 
     if (result instanceof Error) {
       toast.error(result.message, { position: "bottom-right" });
+      if (
+        "supportsPassword" in platforms[platform] &&
+        isPasswordError(result)
+      ) {
+        setPasswordRequest({ platform, value: parsedInput, index });
+      }
     } else {
       setParsedResult(result);
     }
@@ -97,6 +110,8 @@ export const ParsingContextProvider = (props: ParentProps) => {
         isHistoryItemParsing,
         isSearchParsing,
         onParse,
+        passwordRequest,
+        setPasswordRequest,
       }}
     >
       {props.children}
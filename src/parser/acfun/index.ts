@@ -0,0 +1,31 @@
+import { parseAcfun } from "~/commands/parser";
+
+import LiveStreamParser from "../base";
+import { parseRoomID } from "../utils";
+
+class AcfunParser extends LiveStreamParser {
+  constructor(roomID: number) {
+    super(roomID, "https://live.acfun.cn/live/");
+  }
+
+  async parse(): Promise<ParsedResult | Error> {
+    try {
+      const result = await parseAcfun(this.roomID);
+      return result;
+    } catch (e) {
+      return Error(String(e));
+    }
+  }
+}
+
+export default function createAcfunParser(
+  input: string | number,
+): AcfunParser | Error {
+  // 链接形式为 live.acfun.cn/live/<uid>
+  const roomID = parseRoomID(input, 2);
+  if (roomID instanceof Error) {
+    return roomID;
+  }
+
+  return new AcfunParser(roomID);
+}
@@ -1,4 +1,5 @@
 import type { NOT_LIVE } from ".";
+import { INVALID_INPUT } from "./utils";
 
 export default abstract class LiveStreamParser {
   protected roomID: number;
@@ -15,3 +16,42 @@ export default abstract class LiveStreamParser {
     return this.baseURL + this.roomID.toString();
   }
 }
+
+type ParseByURL = (url: string, password?: string) => Promise<ParsedResult>;
+
+// 房间号不是数字的平台只能通过链接解析，链接原样交给后端
+export class URLParser extends LiveStreamParser {
+  url: string;
+  password: string;
+  command: ParseByURL;
+
+  constructor(
+    baseURL: string,
+    command: ParseByURL,
+    url: string,
+    password = "",
+  ) {
+    super(0, baseURL);
+    this.command = command;
+    this.url = url;
+    this.password = password;
+  }
+
+  async parse(): Promise<ParsedResult | Error> {
+    try {
+      const result = await this.command(this.url, this.password);
+      return result;
+    } catch (e) {
+      return Error(String(e));
+    }
+  }
+}
+
+// 创建只接受链接的解析器，数字输入无法对应到房间
+export const createURLParser =
+  (baseURL: string, command: ParseByURL) =>
+  (input: string | number, password?: string): URLParser | Error => {
+    if (typeof input === "number") return INVALID_INPUT;
+
+    return new URLParser(baseURL, command, input, password);
+  };
@@ -2,16 +2,17 @@ import { parseBigo } from "~/commands/parser";
 
 import LiveStreamParser from "../base";
 
-import { parseRoomID } from "../utils";
-
 class BigoParser extends LiveStreamParser {
-  constructor(roomID: number) {
+  url: string;
+
+  constructor(roomID: number, url = "") {
     super(roomID, "https://www.bigo.tv/cn/");
+    this.url = url;
   }
 
   async parse(): Promise<ParsedResult | Error> {
     try {
-      const result = await parseBigo(this.roomID);
+      const result = await parseBigo(this.roomID, this.url);
       return result;
     } catch (e) {
       return Error(String(e));
@@ -20,8 +21,8 @@ class BigoParser extends LiveStreamParser {
 }
 
 export default function createBigoParser(input: string | number) {
-  const roomID = parseRoomID(input);
-  if (roomID instanceof Error) return roomID;
+  // 链接中可能是用户名而不是数字 ID，由后端解析
+  if (typeof input === "number") return new BigoParser(input);
 
-  return new BigoParser(roomID);
+  return new BigoParser(0, input);
 }
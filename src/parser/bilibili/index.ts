@@ -5,15 +5,23 @@ import LiveStreamParser from "../base";
 class BilibiliParser extends LiveStreamParser {
   cookie: string;
   url: string;
-  constructor(cookie: string, roomID = 0, url = "") {
+  password: string;
+  constructor(cookie: string, roomID = 0, url = "", password = "") {
     super(roomID, "");
     this.cookie = cookie;
     this.url = url;
+    this.password = password;
   }
 
   async parse(): Promise<ParsedResult | Error> {
     try {
-      const result = await parseBilibili(this.roomID, this.cookie, this.url);
+      const result = await parseBilibili(
+        this.roomID,
+        this.cookie,
+        this.url,
+        {},
+        this.password,
+      );
       return result;
     } catch (e) {
       return Error(String(e));
@@ -24,6 +32,7 @@ class BilibiliParser extends LiveStreamParser {
 export default function createBilibiliParser(
   input: string | number,
   cookie: string,
+  password?: string,
 ) {
   let roomID: number | undefined;
   let url: string | undefined;
@@ -31,5 +40,5 @@ export default function createBilibiliParser(
   if (typeof input === "number") roomID = input;
   else url = input;
 
-  return new BilibiliParser(cookie, roomID, url);
+  return new BilibiliParser(cookie, roomID, url, password);
 }
@@ -0,0 +1,30 @@
+import { parseCC } from "~/commands/parser";
+
+import LiveStreamParser from "../base";
+import { parseRoomID } from "../utils";
+
+class CCParser extends LiveStreamParser {
+  constructor(roomID: number) {
+    super(roomID, "https://cc.163.com/");
+  }
+
+  async parse(): Promise<ParsedResult | Error> {
+    try {
+      const result = await parseCC(this.roomID);
+      return result;
+    } catch (e) {
+      return Error(String(e));
+    }
+  }
+}
+
+export default function createCCParser(
+  input: string | number,
+): CCParser | Error {
+  const roomID = parseRoomID(input);
+  if (roomID instanceof Error) {
+    return roomID;
+  }
+
+  return new CCParser(roomID);
+}
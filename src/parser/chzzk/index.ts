@@ -0,0 +1,6 @@
+import { parseChzzk } from "~/commands/parser";
+
+import { createURLParser } from "../base";
+
+// 频道 ID 是十六进制字符串，只能通过链接解析
+export default createURLParser("https://chzzk.naver.com/live/", parseChzzk);
@@ -2,16 +2,17 @@ import { parseDouyin } from "~/commands/parser";
 
 import LiveStreamParser from "../base";
 
-import { parseRoomID } from "../utils";
-
 class DouyinParser extends LiveStreamParser {
-  constructor(roomID: number) {
+  url: string;
+
+  constructor(roomID = 0, url = "") {
     super(roomID, "https://live.douyin.com/");
+    this.url = url;
   }
 
   async parse(): Promise<ParsedResult | Error> {
     try {
-      const result = await parseDouyin(this.roomID);
+      const result = await parseDouyin(this.roomID, this.url);
       return result;
     } catch (e) {
       return Error(String(e));
@@ -20,8 +21,11 @@ class DouyinParser extends LiveStreamParser {
 }
 
 export default function createDouyinParser(input: string | number) {
-  const roomID = parseRoomID(input);
-  if (roomID instanceof Error) return roomID;
+  let roomID: number | undefined;
+  let url: string | undefined;
+
+  if (typeof input === "number") roomID = input;
+  else url = input;
 
-  return new DouyinParser(roomID);
+  return new DouyinParser(roomID, url);
 }
@@ -1,12 +1,20 @@
-import type { Setter } from "solid-js";
-
 import { error } from "~/command";
 
+import acfun from "./acfun";
 import bilibili from "./bilibili";
+import cc from "./cc";
+import chzzk from "./chzzk";
 import douyin from "./douyin";
 import bigo from "./bigo";
 import douyu from "./douyu";
 import huya from "./huya";
+import kuaishou from "./kuaishou";
+import niconico from "./niconico";
+import soop from "./soop";
+import tiktok from "./tiktok";
+import twitcasting from "./twitcasting";
+import twitch from "./twitch";
+import youtube from "./youtube";
 import yy from "./yy";
 import type LiveStreamParser from "./base";
 import { getSecondLevelDomain, WRONG_SECOND_LEVEL_DOMAIN } from "./utils";
@@ -14,30 +22,47 @@ import { getSecondLevelDomain, WRONG_SECOND_LEVEL_DOMAIN } from "./utils";
 export const NOT_LIVE = Error("当前直播间未开播");
 export const IS_REPLAY = Error("当前直播间正在重播，本程序不解析重播源");
 
+// 与后端 RoomStateError 的提示一致，出现时提示用户输入房间密码
+const PASSWORD_ERROR_MESSAGES = [
+  "该房间已加密，需要输入房间密码",
+  "房间密码错误",
+];
+
+export const isPasswordError = (e: Error) =>
+  PASSWORD_ERROR_MESSAGES.includes(e.message);
+
+// numericRoomID 为 true 的平台，历史记录中的房间号是数字，可以直接用于解析；
+// 其他平台的房间号（如用户名、频道名）需要拼接成链接再解析
 export const platforms = {
   douyu: {
     label: "斗鱼",
     roomBaseURL: "https://www.douyu.com/",
     logo: "https://www.douyu.com/favicon.ico",
     parser: douyu,
+    numericRoomID: true,
   },
   huya: {
     label: "虎牙",
     roomBaseURL: "https://www.huya.com/",
     logo: "https://www.huya.com/favicon.ico",
     parser: huya,
+    numericRoomID: true,
   },
   bilibili: {
     label: "B 站",
     roomBaseURL: "https://live.bilibili.com/",
     logo: "https://www.bilibili.com/favicon.ico",
     parser: bilibili,
+    numericRoomID: true,
+    // 加密直播间需要输入房间密码
+    supportsPassword: true,
   },
   douyin: {
     label: "抖音",
     roomBaseURL: "https://live.douyin.com/",
     logo: "https://p-pc-weboff.byteimg.com/tos-cn-i-9r5gewecjs/favicon.png",
     parser: douyin,
+    numericRoomID: true,
   },
   yy: {
     label: "YY",
@@ -51,8 +76,83 @@ export const platforms = {
     logo: "https://s2.loli.net/2025/08/31/DZgyj189JrkAHCs.png",
     parser: bigo,
   },
+  kuaishou: {
+    label: "快手",
+    roomBaseURL: "https://live.kuaishou.com/u/",
+    logo: "https://live.kuaishou.com/favicon.ico",
+    parser: kuaishou,
+  },
+  twitch: {
+    label: "Twitch",
+    roomBaseURL: "https://www.twitch.tv/",
+    logo: "https://www.twitch.tv/favicon.ico",
+    parser: twitch,
+  },
+  youtube: {
+    label: "YouTube",
+    // 历史记录中保存的是频道 ID
+    roomBaseURL: "https://www.youtube.com/channel/",
+    logo: "https://www.youtube.com/favicon.ico",
+    parser: youtube,
+  },
+  cc: {
+    label: "CC",
+    roomBaseURL: "https://cc.163.com/",
+    logo: "https://cc.163.com/favicon.ico",
+    parser: cc,
+    numericRoomID: true,
+  },
+  acfun: {
+    label: "AcFun",
+    roomBaseURL: "https://live.acfun.cn/live/",
+    logo: "https://www.acfun.cn/favicon.ico",
+    parser: acfun,
+    numericRoomID: true,
+  },
+  soop: {
+    label: "SOOP",
+    roomBaseURL: "https://play.sooplive.co.kr/",
+    logo: "https://www.sooplive.co.kr/favicon.ico",
+    parser: soop,
+    // 加密直播需要输入直播密码
+    supportsPassword: true,
+  },
+  chzzk: {
+    label: "CHZZK",
+    roomBaseURL: "https://chzzk.naver.com/live/",
+    logo: "https://chzzk.naver.com/favicon.ico",
+    parser: chzzk,
+  },
+  tiktok: {
+    label: "TikTok",
+    roomBaseURL: "https://www.tiktok.com/@",
+    logo: "https://www.tiktok.com/favicon.ico",
+    parser: tiktok,
+  },
+  niconico: {
+    label: "ニコニコ生放送",
+    roomBaseURL: "https://live.nicovideo.jp/watch/",
+    logo: "https://live.nicovideo.jp/favicon.ico",
+    parser: niconico,
+  },
+  twitcasting: {
+    label: "TwitCasting",
+    roomBaseURL: "https://twitcasting.tv/",
+    logo: "https://twitcasting.tv/favicon.ico",
+    parser: twitcasting,
+    // 加密直播需要输入直播密码
+    supportsPassword: true,
+  },
 } as const;
 
+// 短链使用的其他二级域名
+const DOMAIN_ALIASES: Partial<Record<Platform, string[]>> = {
+  youtube: ["youtu"],
+  // 旧的 AfreecaTV 域名
+  soop: ["afreecatv"],
+  niconico: ["nico"],
+};
+
 export const handleParsingError = (platform: Platform, e: unknown): Error => {
   const errorMessage = String(e);
   switch (errorMessage) {
@@ -76,25 +176,29 @@ export const parse = async (
   platform: Platform,
   input: string | number,
   config: Config,
-  setShowSettings: Setter<boolean>,
+  password?: string,
 ) => {
-  if (
-    typeof input === "string" &&
-    getSecondLevelDomain(platforms[platform].roomBaseURL) !==
-      getSecondLevelDomain(input)
-  ) {
-    return new Error(WRONG_SECOND_LEVEL_DOMAIN.message);
+  if (typeof input === "string") {
+    const domain = getSecondLevelDomain(input);
+    if (
+      getSecondLevelDomain(platforms[platform].roomBaseURL) !== domain &&
+      !DOMAIN_ALIASES[platform]?.includes(domain)
+    ) {
+      return new Error(WRONG_SECOND_LEVEL_DOMAIN.message);
+    }
   }
 
   let parser: LiveStreamParser | Error;
 
   if (platform === "bilibili") {
-    if (!config.platform.bilibili.cookie.length) {
-      setShowSettings(true);
-      return;
-    }
-
-    parser = platforms.bilibili.parser(input, config.platform.bilibili.cookie);
+    // 没有 cookie 时以游客身份解析，只能获取较低画质
+    parser = platforms.bilibili.parser(
+      input,
+      config.platform.bilibili.cookie,
+      password,
+    );
+  } else if (platform === "soop" || platform === "twitcasting") {
+    parser = platforms[platform].parser(input, password);
   } else {
     parser = platforms[platform!].parser(input);
   }
@@ -0,0 +1,30 @@
+import { parseKuaishou } from "~/commands/parser";
+
+import LiveStreamParser from "../base";
+
+class KuaishouParser extends LiveStreamParser {
+  url: string;
+
+  constructor(roomID: number, url = "") {
+    super(roomID, "https://live.kuaishou.com/u/");
+    this.url = url;
+  }
+
+  async parse(): Promise<ParsedResult | Error> {
+    try {
+      const result = await parseKuaishou(this.roomID, this.url);
+      return result;
+    } catch (e) {
+      return Error(String(e));
+    }
+  }
+}
+
+export default function createKuaishouParser(
+  input: string | number,
+): KuaishouParser | Error {
+  // 主播 ID 可能含字母，链接由后端解析
+  if (typeof input === "number") return new KuaishouParser(input);
+
+  return new KuaishouParser(0, input);
+}
@@ -0,0 +1,9 @@
+import { parseNiconico } from "~/commands/parser";
+
+import { createURLParser } from "../base";
+
+// 节目、频道和用户 ID 带有前缀，只能通过链接解析
+export default createURLParser(
+  "https://live.nicovideo.jp/watch/",
+  parseNiconico,
+);
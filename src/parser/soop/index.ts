@@ -0,0 +1,10 @@
+import { parseSoop } from "~/commands/parser";
+
+import { createURLParser } from "../base";
+
+// BJ ID 是字符串，只能通过链接解析；
+// 界面中没有画质选择，与其他平台一样请求默认的最高画质
+export default createURLParser(
+  "https://play.sooplive.co.kr/",
+  (url, password) => parseSoop(url, undefined, password),
+);
@@ -0,0 +1,6 @@
+import { parseTiktok } from "~/commands/parser";
+
+import { createURLParser } from "../base";
+
+// 用户名不是数字，只能通过链接解析
+export default createURLParser("https://www.tiktok.com/@", parseTiktok);
@@ -0,0 +1,6 @@
+import { parseTwitcasting } from "~/commands/parser";
+
+import { createURLParser } from "../base";
+
+// 用户 ID 是字符串，只能通过链接解析
+export default createURLParser("https://twitcasting.tv/", parseTwitcasting);
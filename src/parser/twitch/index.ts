@@ -0,0 +1,8 @@
+import { parseTwitch } from "~/commands/parser";
+
+import { URLParser } from "../base";
+
+export default function createTwitchParser(input: string | number) {
+  // 频道名是字符串，纯数字的输入也按频道名处理
+  return new URLParser("https://www.twitch.tv/", parseTwitch, String(input));
+}
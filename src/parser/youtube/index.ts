@@ -0,0 +1,9 @@
+import { parseYoutube } from "~/commands/parser";
+
+import { createURLParser } from "../base";
+
+// YouTube 没有数字房间号
+export default createURLParser(
+  "https://www.youtube.com/channel/",
+  parseYoutube,
+);
@@ -1,16 +1,18 @@
 import { parseYY } from "~/commands/parser";
 
 import LiveStreamParser from "../base";
-import { parseRoomID } from "../utils";
 
 class YYParser extends LiveStreamParser {
-  constructor(roomID: number) {
+  url: string;
+
+  constructor(roomID: number, url = "") {
     super(roomID, "https://www.yy.com/");
+    this.url = url;
   }
 
   async parse(): Promise<ParsedResult | Error> {
     try {
-      const result = await parseYY(this.roomID);
+      const result = await parseYY(this.roomID, this.url);
       return result;
     } catch (error) {
       return error instanceof Error ? error : new Error(String(error));
@@ -21,10 +23,8 @@ class YYParser extends LiveStreamParser {
 export default function createYYParser(
   input: string | number,
 ): YYParser | Error {
-  const roomID = parseRoomID(input);
-  if (roomID instanceof Error) {
-    return roomID;
-  }
+  // 链接中的子频道号和手机端、分享链接由后端解析
+  if (typeof input === "number") return new YYParser(input);
 
-  return new YYParser(roomID);
+  return new YYParser(0, input);
 }
@@ -6,5 +6,9 @@ interface Player {
 interface Config {
   dark_mode: "dark" | "light" | "system";
   player: Player;
-  platform: { bilibili: { cookie: string } };
+  platform: {
+    bilibili: { cookie: string; refresh_token?: string };
+    yy?: { uid: number };
+    chzzk?: { cookie: string };
+  };
 }
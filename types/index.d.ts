@@ -1,17 +1,56 @@
-type Platform = "douyu" | "huya" | "bilibili" | "douyin" | "bigo";
+type Platform =
+  | "douyu"
+  | "huya"
+  | "bilibili"
+  | "douyin"
+  | "bigo"
+  | "yy"
+  | "kuaishou"
+  | "twitch"
+  | "youtube"
+  | "cc"
+  | "acfun"
+  | "soop"
+  | "chzzk"
+  | "tiktok"
+  | "niconico"
+  | "twitcasting";
 
 interface ParsedResult {
   platform: Platform;
   title: string;
   anchor: string;
-  roomID: number;
+  roomID: string;
   category: string;
-  links: string[];
+  links: Link[];
+  qualities: Quality[];
+  guest: boolean;
+  /** 预定直播尚未开始时的开始时间（Unix 时间戳，秒） */
+  scheduledStart?: number;
+  /** 后端保活的观看会话 ID（Niconico） */
+  keepAliveSession?: number;
 }
 
-interface HistoryItem extends Omit<ParsedResult, "links" | "title" | "roomID"> {
+interface Link {
+  url: string;
+  quality?: string;
+  cdn?: string;
+  format?: string;
+  codec?: string;
+}
+
+interface Quality {
+  value: number;
+  name: string;
+}
+
+interface HistoryItem
+  extends Omit<
+    ParsedResult,
+    "links" | "qualities" | "guest" | "title" | "roomID" | "scheduledStart"
+  > {
   id: number;
   last_play_time: Date;
   last_title: string;
-  room_id: number;
+  room_id: string;
 }